@@ -12,6 +12,213 @@ generate-button = Generate!
 save-button = Save OC
 saved-message = { $num } saved characters
 favorites = Favorites
+# The generated OC as one sentence. Every trait is passed in under its pack category
+# ($attribute, $species, $characteristic, $name, $pronouns, $age, $backstory), and every
+# attribute of the trait's message as $category-attribute. English only needs
+# $attribute-article below, guessed from the text unless the entry sets it. Other languages
+# can select on $species-gender to pick articles and adjective endings, e.g. with
+# `.gender = feminine` on species and `.feminine = kleine` on attributes:
+#   { $species-gender -> [feminine] Eine { $attribute-feminine } *[masculine] Ein { $attribute-masculine } } ...
+# Rules from generator packs can still be used as #rule#, see src/pages/oc_generator/grammar.rs.
+oc-sentence = { $attribute-article ->
+        [an] An
+       *[a] A
+    } { $attribute } { $species } { $characteristic }
+packs = Packs:
+pack-errors = Some generator packs could not be loaded
+seed-code = Seed code: { $code }
+seed-placeholder = Seed code
+generate-from-code-button = Generate from code
+seed-invalid = That doesn't look like a seed code, they're 8 letters and numbers
+seed-packs-changed = Your packs changed since this OC was saved, so it may come out different
+seed-rating-changed = This OC was saved with a different content rating, so it may come out different
+seed-hybrids-changed = This OC was saved with different hybrid settings, so its species may come out different
+seed-names-changed = Made-up names were switched the other way when this OC was saved, so its name will come out different
+slot-attribute = Attribute
+slot-species = Species
+slot-characteristic = Characteristic
+slot-name = Name
+slot-pronouns = Pronouns
+slot-age = Age
+slot-backstory = Backstory
+sheet-name = Name
+sheet-pronouns = Pronouns
+sheet-age = Age
+sheet-species = Species
+sheet-parent-species = Hybrid of
+sheet-attributes = Attributes
+sheet-quirks = Quirks
+sheet-backstory = Backstory hook
+sheet-description = Description
+sheet-notes = Notes
+sheet-list-hint = Separate multiple attributes, quirks or tags with commas
+details-button = Details
+edit-button = Edit
+back-button = Back
+cancel-button = Cancel
+save-changes-button = Save changes
+save-file-title = Save file
+
+# Palettes
+harmony-complementary = Complementary
+harmony-analogous = Analogous
+harmony-triadic = Triadic
+swatch-main = Main
+swatch-accent = Accent
+swatch-marking = Markings
+export-hex-button = Hex list
+export-gpl-button = GIMP palette
+
+# Content rating
+mature-content = Mature content
+hidden-character = Hidden because it uses mature content
+reveal-button = Reveal
+
+# Hybrids
+hybrids-off = No hybrids
+hybrid-chance = { $percent }% hybrids
+hybrid-naming-portmanteau = Blend names (folf)
+hybrid-naming-descriptive = Describe (fox/wolf hybrid)
+
+# Favorites search
+search-placeholder = Search favorites
+sort-newest = Newest first
+sort-oldest = Oldest first
+sort-alphabetical = A to Z
+sort-manual = My order
+pin-button = Pin to top
+unpin-button = Unpin
+move-up-button = Move up
+move-down-button = Move down
+saved-on = Saved { $date }
+
+# Tags and collections
+tags = Tags
+collections = Collections
+all-characters = All characters
+new-collection-placeholder = Collection name
+new-collection-button = New collection
+
+# Trash
+trash = Trash
+trash-button = Trash ({ $count })
+trash-retention-label = Keep deleted characters for
+trash-retention = { $days } days
+deleted-on = Deleted { $date }
+restore-button = Restore
+empty-trash-button = Empty trash
+character-deleted = Character moved to the trash
+undo-button = Undo
+
+# Relationships
+relationships = Relationships
+relationship-friend = Friend
+relationship-rival = Rival
+relationship-sibling = Sibling
+relationship-partner = Partner
+relationship-custom = Custom…
+relationship-custom-placeholder = Mentor, roommate…
+relationship-with = with
+add-relationship-button = Add
+relationship-graph-button = Relationships
+no-relationships = No relationships yet. Add some from a character's page.
+
+# History
+history = History
+keep-history = Remember across restarts
+generate-batch-button = Generate batch
+batch-size = { $count } OCs
+batch = Batch of { $count }
+saved = Saved
+save-selected-button = Save selected ({ $count })
+
+# Parties
+generate-party-button = Generate party
+party-size = Party of { $count }
+party = Party of { $count }
+party-name-placeholder = Collection name
+party-default-name = { $leader }'s party
+save-party-button = Save party
+party-bond = { $first } and { $second }: { $kind }
+party-role = Role
+role-leader = Leader
+role-comic-relief = Comic relief
+role-rival = Rival
+role-mentor = Mentor
+role-heart = Heart of the group
+role-brains = Brains
+role-muscle = Muscle
+role-wildcard = Wildcard
+
+# More like this
+like-button = More like this
+dislike-button = Less like this
+surprise-me = Surprise me
+more-like-favorites = More like my favorites
+weights-button = Likes ({ $count })
+reset-weights-button = Reset likes
+weight = ×{ $weight }
+no-weights = Nothing liked or disliked yet
+
+# Export and import
+format-json = JSON
+format-markdown = Markdown
+format-csv = CSV
+format-plain-text = Plain text
+format-discord = Discord
+copy-button = Copy
+copy-as = Copy as { $format }
+copied = Copied as { $format }
+copy-failed = Couldn't copy the character
+character-saved = Saved to favorites
+
+# Made-up names
+generated-names = Made-up names
+name-ideas = Name ideas
+suggest-names-button = Suggest names
+copy-name = Copy name
+copied-name = Copied { $name }
+name-flavor-common = Common
+name-flavor-draconic = Draconic
+name-flavor-feline = Feline
+name-flavor-canine = Canine
+name-flavor-woodland = Woodland
+name-flavor-nocturnal = Nocturnal
+export-all = Export all:
+export-selected = Export { $count } selected:
+export-file-name = characters
+palette = Palette
+export-failed = Couldn't export: { $error }
+file-saved = Saved { $file }
+file-save-failed = Couldn't save the file: { $error }
+import-button = Import
+open-file-title = Open file
+import-summary = Imported { $added ->
+        [one] 1 character
+       *[other] { $added } characters
+    }, skipped { $skipped } already saved
+import-added = Added: { $names }
+import-skipped = Skipped: { $names }
+import-failed = Couldn't import that file
+import-invalid-color = "{ $color }" isn't a color like #FF8800
+export-card-svg-button = Card (SVG)
+export-card-png-button = Card (PNG)
+export-commission-md-button = Commission (Markdown)
+export-commission-txt-button = Commission (Text)
+edit-templates-button = Edit commission templates
+commission-file-name = { $name } commission.{ $extension }
+no-config-dir = Couldn't find a config folder for the templates
+card-branding = Made with Cosmi-Kit
+cli-usage = Usage:
+    cosmi-kit card <id or name> [--output card.svg|card.png]    Render a saved OC's reference card, SVG goes to stdout without --output
+    cosmi-kit commission <id or name> [--output sheet.md|sheet.txt]    Fill in a commission sheet, Markdown goes to stdout without --output
+    cosmi-kit list    List saved OCs with their ids
+    cosmi-kit names [flavor] [--count 12]    Make up names, flavors are common, draconic, feline, canine, woodland and nocturnal
+cli-not-found = No saved OC matches "{ $query }"
+cli-ambiguous = { $count } saved OCs match "{ $query }", use a longer name or the id from `cosmi-kit list`
+cli-unknown-flavor = No name flavor called "{ $flavor }", try one of: { $flavors }
+
+# The English sentence guesses a/an from the attribute, `.article` sets it where the guess is wrong
 attribute-short = short
 attribute-tall = tall
 attribute-fat = fat
@@ -21,36 +228,65 @@ attribute-shy = shy
 attribute-curious = curious
 attribute-friendly = friendly
 attribute-aloof = aloof
+    .article = an
 attribute-clever = clever
 attribute-clumsy = clumsy
 attribute-energetic = energetic
+    .article = an
 attribute-sleepy = sleepy
 attribute-grumpy = grumpy
 attribute-optimistic = optimistic
+    .article = an
 attribute-pessimistic = pessimistic
 attribute-cunning = cunning
 attribute-kind = kind
 attribute-sarcastic = sarcastic
 attribute-micro = micro
 attribute-macro = macro
+# English has no grammatical gender, translations set `.gender` to whatever their
+# oc-sentence selects on (masculine, feminine, neuter...)
 species-cat = cat
+    .gender = neuter
 species-dog = dog
+    .gender = neuter
 species-fox = fox
+    .gender = neuter
 species-wolf = wolf
+    .gender = neuter
 species-dragon = dragon
+    .gender = neuter
 species-ferret = ferret
+    .gender = neuter
 species-rabbit = rabbit
+    .gender = neuter
 species-hare = hare
+    .gender = neuter
 species-bat = bat
+    .gender = neuter
 species-horse = horse
+    .gender = neuter
 species-pony = pony
+    .gender = neuter
 species-deer = deer
+    .gender = neuter
 species-avian = avian
+    .gender = neuter
 species-lion = lion
+    .gender = neuter
 species-tiger = tiger
+    .gender = neuter
 species-hyena = hyena
+    .gender = neuter
 species-snake = snake
-species-folf = fox/wolf hybrid
+    .gender = neuter
+species-folf = folf
+    .gender = neuter
+species-liger = liger
+    .gender = neuter
+species-tigon = tigon
+    .gender = neuter
+species-hybrid = { $first }/{ $second } hybrid
+    .gender = neuter
 characteristic-mokawk = with a mohawk
 characteristic-no-pants = with no pants
 characteristic-constant-waffles = constantly eating waffles
@@ -67,6 +303,29 @@ characteristic-collects-bad-jokes = who collects dad jokes religiously
 characteristic-vore = who turns every meal into a vore joke
 characteristic-ponytail = with long hair bound into a ponytail
 characteristic-sparkle = whose fur has so many shades it makes sparkledogs blush
+pronouns-she = she/her
+pronouns-he = he/him
+pronouns-they = they/them
+pronouns-xe = xe/xem
+pronouns-it = it/its
+pronouns-any = any pronouns
+age-young-adult = young adult
+age-twenties = in their twenties
+age-thirties = in their thirties
+age-middle-aged = middle-aged
+age-elder = elderly
+age-ancient = centuries old
+age-unknown = nobody knows, not even them
+backstory-lost-royalty = is secretly royalty on the run
+backstory-failed-wizard = flunked out of wizard school and is still bitter about it
+backstory-space-courier = delivers packages across the galaxy, mostly on time
+backstory-cursed = was cursed by a very petty witch
+backstory-cafe-front = runs a cozy cafe that is definitely a front for something
+backstory-amnesia = woke up with no memories and a mysterious map
+backstory-retired-hero = is a retired hero dragged back for one last job
+backstory-streamer = is trying to make it big as a streamer
+backstory-lighthouse = keeps a lighthouse nobody remembers building
+backstory-rival = swore revenge on their childhood rival over a stolen sandwich
 oc-generator = OC generator
 project-manager = Project manager
 dice-roller = Dice Roller
@@ -98,26 +98,28 @@ impl cosmic::Application for AppModel {
             .data::<Page>(Page::DiceRoller)
             .icon(icon::from_name("applications-games-symbolic"));
 
+        // Optional configuration file for an application.
+        let config = cosmic_config::Config::new(Self::APP_ID, Config::VERSION)
+            .map(|context| match Config::get_entry(&context) {
+                Ok(config) => config,
+                Err((_errors, config)) => {
+                    // for why in errors {
+                    //     tracing::error!(%why, "error loading app config");
+                    // }
+
+                    config
+                }
+            })
+            .unwrap_or_default();
+
         // Construct the app model with the runtime's core.
         let mut app = AppModel {
             core,
             context_page: ContextPage::default(),
-            oc_generator_page: OcGeneratorPage::default(),
+            oc_generator_page: OcGeneratorPage::new(config.clone()),
             nav,
             key_binds: HashMap::new(),
-            // Optional configuration file for an application.
-            config: cosmic_config::Config::new(Self::APP_ID, Config::VERSION)
-                .map(|context| match Config::get_entry(&context) {
-                    Ok(config) => config,
-                    Err((_errors, config)) => {
-                        // for why in errors {
-                        //     tracing::error!(%why, "error loading app config");
-                        // }
-
-                        config
-                    }
-                })
-                .unwrap_or_default(),
+            config,
         };
 
         // Create a startup command that sets the window title.
@@ -248,9 +250,15 @@ impl cosmic::Application for AppModel {
     fn update(&mut self, message: Self::Message) -> Task<cosmic::Action<Self::Message>> {
         match message {
             Message::OcGeneratorPage(page_message) => {
-                //Make the page active
-                // return self.oc_generator_page.update(page_message);
-                let _ = self.oc_generator_page.update(page_message);
+                // Pages can hand back tasks (like file dialogs), wrap their messages so they find their way back
+                return self
+                    .oc_generator_page
+                    .update(page_message)
+                    .map(|action| match action {
+                        cosmic::Action::App(message) => cosmic::Action::App(Message::OcGeneratorPage(message)),
+                        cosmic::Action::Cosmic(action) => cosmic::Action::Cosmic(action),
+                        _ => cosmic::Action::None,
+                    });
             }
 
             Message::OpenRepositoryUrl => {
@@ -273,6 +281,10 @@ impl cosmic::Application for AppModel {
             }
 
             Message::UpdateConfig(config) => {
+                // Pages keep their own copy of the settings they care about
+                let _ = self
+                    .oc_generator_page
+                    .update(oc_generator::Message::UpdateConfig(config.clone()));
                 self.config = config;
             }
 
@@ -0,0 +1,194 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Command line mode
+//!
+//! `cosmi-kit card <id or name>` renders a saved OC's reference card without opening a window,
+//! for scripts and for making cards in bulk. `cosmi-kit commission` does the same for commission
+//! sheets, and `cosmi-kit names` brainstorms names. Anything else starts the app as usual.
+
+use crate::fl;
+use crate::pages::oc_generator::card::{self, CardFormat};
+use crate::pages::oc_generator::commission::{self, CommissionFormat};
+use crate::pages::oc_generator::names;
+use crate::pages::oc_generator::packs::{self, WordPools};
+use crate::pages::oc_generator::{self, SavedOC};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Run a command if the arguments ask for one, returning the exit code
+///
+/// Returns `None` when there is no command so `main` can start the app instead.
+pub fn run(mut args: impl Iterator<Item = String>) -> Option<i32> {
+    let result = match args.next()?.as_str() {
+        "card" => card_command(args),
+        "commission" => commission_command(args),
+        "list" => list_command(),
+        "names" => names_command(args),
+        "help" | "--help" | "-h" => {
+            println!("{}", fl!("cli-usage"));
+            Ok(())
+        }
+        _ => return None,
+    };
+
+    Some(match result {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("{e}");
+            1
+        }
+    })
+}
+
+fn card_command(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let (query, output) = query_and_output(args)?;
+
+    let characters = load_characters();
+    let character = find_character(&characters, &query)?;
+
+    match output {
+        // The extension picks the format, anything that isn't .png gets SVG
+        Some(path) => {
+            let format = match path.extension().and_then(|e| e.to_str()) {
+                Some(extension) if extension.eq_ignore_ascii_case("png") => CardFormat::Png,
+                _ => CardFormat::Svg,
+            };
+            let contents = card::render(character, format)?;
+            std::fs::write(&path, contents).map_err(|e| e.to_string())
+        }
+        None => std::io::stdout()
+            .write_all(card::render_svg(character).as_bytes())
+            .map_err(|e| e.to_string()),
+    }
+}
+
+fn commission_command(args: impl Iterator<Item = String>) -> Result<(), String> {
+    let (query, output) = query_and_output(args)?;
+
+    let characters = load_characters();
+    let character = find_character(&characters, &query)?;
+
+    // The extension picks the format, anything that isn't .txt gets Markdown
+    let format = match output.as_ref().and_then(|path| path.extension()).and_then(|e| e.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("txt") => CommissionFormat::PlainText,
+        _ => CommissionFormat::Markdown,
+    };
+    // Every pack, switched off ones may still know the character's attributes
+    let (packs, errors) = packs::load_packs();
+    for error in &errors {
+        eprintln!("Error loading generator pack: {error}");
+    }
+    let pools = WordPools::merge(&packs, &[]);
+    let sheet = commission::render(character, format, &commission::template(format), &pools);
+    match output {
+        Some(path) => std::fs::write(&path, sheet).map_err(|e| e.to_string()),
+        None => std::io::stdout().write_all(sheet.as_bytes()).map_err(|e| e.to_string()),
+    }
+}
+
+fn list_command() -> Result<(), String> {
+    for character in &load_characters() {
+        println!("{}\t{}", character.id, character.sheet.title());
+    }
+    Ok(())
+}
+
+fn names_command(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut flavor = names::DEFAULT_FLAVOR.to_string();
+    let mut count = names::SUGGESTIONS;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-n" | "--count" => {
+                count = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .ok_or_else(|| fl!("cli-usage"))?;
+            }
+            _ => flavor = arg,
+        }
+    }
+    if !names::FLAVORS.contains(&flavor.as_str()) {
+        return Err(fl!("cli-unknown-flavor", flavor = flavor, flavors = names::FLAVORS.join(", ")));
+    }
+
+    let mut rng = fastrand::Rng::new();
+    for _ in 0..count {
+        println!("{}", names::generate(&flavor, &mut rng));
+    }
+    Ok(())
+}
+
+/// The character to look up and the optional `-o` file, for the commands that render one
+fn query_and_output(mut args: impl Iterator<Item = String>) -> Result<(String, Option<PathBuf>), String> {
+    let mut query = None;
+    let mut output = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-o" | "--output" => {
+                output = Some(args.next().map(PathBuf::from).ok_or_else(|| fl!("cli-usage"))?);
+            }
+            _ => query = Some(arg),
+        }
+    }
+    let query = query.ok_or_else(|| fl!("cli-usage"))?;
+    Ok((query, output))
+}
+
+/// Saved characters, read without writing anything back, none if nothing was saved yet
+fn load_characters() -> Vec<SavedOC> {
+    oc_generator::load_saved_characters().unwrap_or_else(|e| {
+        if !matches!(e, cosmic::cosmic_config::Error::NotFound) {
+            eprintln!("Error loading characters: {:?}", e);
+        }
+        vec![]
+    })
+}
+
+/// Look a character up by id, then by exact name, then by a unique partial name
+fn find_character<'a>(characters: &'a [SavedOC], query: &str) -> Result<&'a SavedOC, String> {
+    if let Some(character) = query
+        .parse::<u64>()
+        .ok()
+        .and_then(|id| characters.iter().find(|c| c.id == id))
+    {
+        return Ok(character);
+    }
+
+    let query_lower = query.to_lowercase();
+    if let Some(character) = characters
+        .iter()
+        .find(|c| c.sheet.title().to_lowercase() == query_lower)
+    {
+        return Ok(character);
+    }
+
+    let partial: Vec<&SavedOC> = characters
+        .iter()
+        .filter(|c| c.sheet.title().to_lowercase().contains(&query_lower))
+        .collect();
+    match partial.as_slice() {
+        [character] => Ok(character),
+        [] => Err(fl!("cli-not-found", query = query)),
+        _ => Err(fl!("cli-ambiguous", query = query, count = partial.len())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn output_needs_a_path() {
+        assert_eq!(
+            query_and_output(args(&["Ash", "-o", "ash.png"])),
+            Ok(("Ash".to_string(), Some(PathBuf::from("ash.png"))))
+        );
+        assert_eq!(query_and_output(args(&["Ash"])), Ok(("Ash".to_string(), None)));
+        assert!(query_and_output(args(&["Ash", "-o"])).is_err());
+        assert!(query_and_output(args(&["--output", "ash.svg"])).is_err());
+    }
+}
@@ -1,30 +1,117 @@
 // SPDX-License-Identifier: MPL-2.0
 
 use cosmic::cosmic_config::{self, cosmic_config_derive::CosmicConfigEntry, ConfigGet, ConfigSet, CosmicConfigEntry};
-use crate::pages::oc_generator::SavedOC;
+use crate::pages::oc_generator::favorites::SortOrder;
+use crate::pages::oc_generator::relationships::Relationship;
+use crate::pages::oc_generator::{SavedOC, TrashedOC};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Default, Clone, CosmicConfigEntry, Eq, PartialEq)]
+#[derive(Debug, Clone, CosmicConfigEntry, Eq, PartialEq)]
 #[version = 1]
 pub struct Config {
     demo: String,
+    /// Ids of generator packs switched off in the OC generator
+    pub disabled_packs: Vec<String>,
+    /// Most explicit pool entries the OC generator may use
+    pub content_rating: ContentRating,
+    /// Days deleted characters stay in the trash before they're purged for good
+    pub trash_retention_days: u32,
+    /// Keep the OC generator's history across restarts
+    pub keep_history: bool,
+    /// How many OCs "Generate batch" rolls at once
+    pub batch_size: u32,
+    /// How many characters "Generate party" rolls
+    pub party_size: u32,
+    /// How strongly fresh rolls lean towards liked entries, 0 (not at all) to 100
+    pub like_bias: u32,
+    /// Likes and dislikes per pack entry, keyed like `species/fox`, in percent of a neutral pick
+    pub entry_weights: BTreeMap<String, u32>,
+    /// Percent chance of a species roll turning into a hybrid of two species
+    pub hybrid_chance: u32,
+    /// What to call hybrids that no pack has a name for
+    pub hybrid_naming: HybridNaming,
+    /// Make up names that fit the species instead of picking them from the packs' name lists
+    pub generated_names: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            demo: String::new(),
+            disabled_packs: vec![],
+            content_rating: ContentRating::default(),
+            trash_retention_days: 30,
+            keep_history: false,
+            batch_size: 10,
+            party_size: 4,
+            like_bias: 0,
+            entry_weights: BTreeMap::new(),
+            // Off by default so seed codes shared before hybrids still roll the same species
+            hybrid_chance: 0,
+            hybrid_naming: HybridNaming::default(),
+            // Off by default so seed codes shared before made-up names still roll the same name
+            generated_names: false,
+        }
+    }
+}
+
+/// How explicit a generator pack entry is, ordered from tamest to spiciest
+#[derive(
+    Debug, Default, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ContentRating {
+    /// Fine for family game night or on stream
+    #[default]
+    Sfw,
+    Mature,
+}
+
+/// How hybrids without a name from a pack get named
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HybridNaming {
+    /// Blend the two names, "fox" and "wolf" make "folf"
+    #[default]
+    Portmanteau,
+    /// Always describe it, like "fox/wolf hybrid"
+    Descriptive,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SavedCharactersConfig {
     pub characters: Vec<SavedOC>,
+    /// Names of the user's collections, kept here too so empty ones don't disappear
+    #[serde(default)]
+    pub collections: Vec<String>,
+    /// Deleted characters waiting to be restored or purged
+    #[serde(default)]
+    pub trash: Vec<TrashedOC>,
+    /// Relationships between characters in `characters`
+    #[serde(default)]
+    pub relationships: Vec<Relationship>,
+    /// How the favorites list is sorted, `characters` is in the user's own order
+    #[serde(default)]
+    pub sort_order: SortOrder,
 }
 
 pub const CONFIG_KEY: &str = "com.github.kitfoxboy.cosmi-kit";
 
+impl SavedCharactersConfig {
+    /// Version before collections, `load_characters` migrates saves from here
+    pub const LEGACY_VERSION: u64 = 1;
+}
+
 impl CosmicConfigEntry for SavedCharactersConfig {
-    const VERSION: u64 = 1;
+    // 2: added tags and collections
+    const VERSION: u64 = 2;
     
     fn write_entry(&self, config: &cosmic_config::Config) -> Result<(), cosmic_config::Error> {
-        config.set("characters", self.characters.clone())
+        config.set("characters", self.clone())
     }
     
     fn get_entry(config: &cosmic_config::Config) -> Result<Self, (Vec<cosmic_config::Error>, Self)> {
-        config.get("characters").map(|characters| Self { characters }).map_err(|e| (vec![e], Self { characters: vec![] }))
+        config.get("characters").map_err(|e| (vec![e], Self { characters: vec![], collections: vec![], trash: vec![], relationships: vec![], sort_order: SortOrder::default() }))
     }
     
     fn update_keys<T: AsRef<str>>(
@@ -39,6 +39,12 @@ pub static LANGUAGE_LOADER: LazyLock<FluentLanguageLoader> = LazyLock::new(|| {
 });
 
 
+/// Drop the bidi isolation marks fluent puts around arguments, for text that gets stored or
+/// exported rather than shown right away
+pub fn strip_isolation_marks(text: &str) -> String {
+    text.replace(['\u{2068}', '\u{2069}'], "")
+}
+
 /// Request a localized string by ID from the i18n/ directory.
 #[macro_export]
 macro_rules! fl {
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: MPL-2.0
 
 mod app;
+mod cli;
 mod config;
 mod i18n;
 mod pages;
@@ -12,6 +13,11 @@ fn main() -> cosmic::iced::Result {
     // Enable localizations to be applied.
     i18n::init(&requested_languages);
 
+    // Commands like `cosmi-kit card` run without ever opening a window
+    if let Some(code) = cli::run(std::env::args().skip(1)) {
+        std::process::exit(code);
+    }
+
     // Settings for configuring the application window and iced runtime.
     let settings = cosmic::app::Settings::default().size_limits(
         cosmic::iced::Limits::NONE
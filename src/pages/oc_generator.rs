@@ -4,13 +4,53 @@
 //!
 //! A fun tool for creating random character concepts for furries, gamers, and creative folks!
 
-use crate::config::{SavedCharactersConfig, CONFIG_KEY};
+mod avatar;
+mod batch;
+mod batch_view;
+pub mod card;
+mod character;
+mod detail_view;
+pub mod commission;
+mod export;
+pub mod favorites;
+mod grammar;
+mod graph;
+mod history;
+mod hybrid;
+pub mod names;
+pub mod packs;
+mod palette;
+mod party;
+mod party_view;
+pub mod relationships;
+mod relationship_view;
+mod seed;
+mod share;
+mod trash_view;
+mod weights;
+
+use crate::config::{Config as AppConfig, ContentRating, HybridNaming, SavedCharactersConfig, CONFIG_KEY};
 use crate::fl;
+use crate::i18n::strip_isolation_marks;
+use batch::Candidate;
+use card::CardFormat;
+use character::{CharacterSheet, GeneratedOC, OcTrait, ParentSpecies, SheetField, TraitSlot};
+use commission::CommissionFormat;
+use export::{ExportFormat, ImportSummary};
+use favorites::SortOrder;
+use history::History;
+use packs::{GeneratorPack, PackEntry, PackError, WordPools};
+use palette::{Palette, PaletteFormat};
+use party::{Member, Party, Role};
+use relationships::{Relationship, RelationshipDraft, RelationshipKind};
+use share::ShareFormat;
 use cosmic::cosmic_config::{Config, ConfigGet, ConfigSet, CosmicConfigEntry};
 use cosmic::iced::alignment::{Horizontal, Vertical};
 use cosmic::iced::Length;
 use cosmic::widget::{self, column, icon, row, text};
 use cosmic::{cosmic_theme, prelude::*, theme};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::PathBuf;
 
 /// Messages that the OC Generator page can emit
 #[derive(Debug, Clone)]
@@ -19,13 +59,253 @@ pub enum Message {
     LoadData,
     GenerateButtonClicked,
     SaveButtonClicked,
-    DeleteCharacter(usize), // Note: In rust, indexes are always of type usize
+    DeleteCharacter(CharacterId),
+    PackToggled(String, bool),
+    UpdateConfig(AppConfig),
+    SeedInputChanged(String),
+    GenerateFromCodeClicked,
+    RebuildCharacter(CharacterId),
+    ToggleLock(TraitSlot),
+    OpenCharacter(CharacterId),
+    CloseCharacter,
+    EditCharacter,
+    SheetFieldChanged(SheetField, String),
+    SaveCharacterEdits,
+    CancelCharacterEdits,
+    /// Export a palette, named after its character
+    ExportPalette(Palette, String, PaletteFormat),
+    ExportCard(CharacterId, CardFormat),
+    ExportCommission(CharacterId, CommissionFormat),
+    EditCommissionTemplates,
+    /// Result of a save dialog, `None` if the user cancelled
+    FileSaved(Result<Option<PathBuf>, String>),
+    MatureContentToggled(bool),
+    /// Index into `hybrid::CHANCES`
+    HybridChanceChanged(usize),
+    HybridNamingChanged(usize),
+    GeneratedNamesToggled(bool),
+    /// Index into `names::FLAVORS`
+    NameFlavorChanged(usize),
+    SuggestNames,
+    CopyName(String),
+    RevealCharacter(CharacterId),
+    SearchChanged(String),
+    SortChanged(usize),
+    TogglePin(CharacterId),
+    /// Move a character one place up (`true`) or down in "my order"
+    MoveCharacter(CharacterId, bool),
+    SpeciesFilterToggled(String),
+    TagFilterToggled(String),
+    TagsChanged(String),
+    /// Index into the collection dropdown, where 0 is "all characters"
+    CollectionSelected(usize),
+    NewCollectionInputChanged(String),
+    CreateCollection,
+    DeleteCollection,
+    CollectionMembershipToggled(String, bool),
+    ShowTrash(bool),
+    RestoreCharacter(CharacterId),
+    PurgeCharacter(CharacterId),
+    EmptyTrash,
+    /// Index into `TRASH_RETENTION_DAYS`
+    TrashRetentionChanged(usize),
+    CloseToast(widget::toaster::ToastId),
+    HistoryBack,
+    HistoryForward,
+    HistoryJump(usize),
+    KeepHistoryToggled(bool),
+    ExportSelectionToggled(CharacterId, bool),
+    ExportCharacters(ExportFormat),
+    ImportClicked,
+    /// Contents of the file picked for import, `None` if the user cancelled
+    ImportLoaded(Result<Option<String>, String>),
+    DismissImportSummary,
+    GenerateBatchClicked,
+    /// Index into `batch::SIZES`
+    BatchSizeChanged(usize),
+    /// Show a candidate as the current OC, to lock traits or export its palette
+    ShowCandidate(usize),
+    RerollCandidate(usize),
+    SaveCandidate(usize),
+    CandidateSelectionToggled(usize, bool),
+    SaveSelectedCandidates,
+    CloseBatch,
+    GeneratePartyClicked,
+    /// Index into `party::SIZES`
+    PartySizeChanged(usize),
+    /// Show a party member as the current OC
+    ShowPartyMember(usize),
+    PartyNameChanged(String),
+    SaveParty,
+    CloseParty,
+    /// Like (`true`) or dislike the current OC
+    RateOc(bool),
+    /// 0 is "surprise me", 100 is "more like my favorites"
+    LikeBiasChanged(u32),
+    LikeBiasReleased,
+    ShowWeights(bool),
+    ResetWeights,
+    /// Index into `ShareFormat::ALL`
+    ShareFormatChanged(usize),
+    CopyCurrentOc,
+    CopyCharacter(CharacterId),
+    ShowRelationshipGraph(bool),
+    /// Index into the relationship targets of the selected character
+    RelationshipTargetSelected(usize),
+    /// Index into `RelationshipKind::PRESETS`, one past the end for a custom kind
+    RelationshipKindSelected(usize),
+    RelationshipCustomChanged(String),
+    AddRelationship,
+    RemoveRelationship(Relationship),
 }
 
+/// Naming rules in the order the dropdown shows them
+const HYBRID_NAMINGS: [HybridNaming; 2] = [HybridNaming::Portmanteau, HybridNaming::Descriptive];
+
+/// Retention periods offered in the trash view, in days
+const TRASH_RETENTION_DAYS: [u32; 4] = [7, 30, 90, 365];
+
+const DAY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+/// Identifies a saved character no matter where it sits in the list
+pub type CharacterId = u64;
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SavedOC {
-    pub text: String,
+    /// Stable id, 0 for saves from before ids until `load_characters` hands out a real one
+    #[serde(default)]
+    pub id: CharacterId,
+    /// Everything on the character's sheet
+    #[serde(default)]
+    pub sheet: CharacterSheet,
     pub created_at: u64,
+    /// Seed the OC was generated from, missing for OCs saved before seeds existed
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// The structured traits, each with the seed it was rolled from
+    #[serde(default)]
+    pub traits: Vec<OcTrait>,
+    /// Versions of the packs that were enabled, keyed by pack id, so we can tell if a rebuild may differ
+    #[serde(default)]
+    pub pack_versions: BTreeMap<String, String>,
+    /// Settings that change what a seed rolls, missing for OCs saved before they were kept
+    #[serde(default)]
+    pub roll_settings: Option<RollSettings>,
+    /// Main, accent and marking colors
+    #[serde(default)]
+    pub palette: Option<Palette>,
+    /// Free-form labels like "fursona" or "commission"
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Names of the collections this character is in
+    #[serde(default)]
+    pub collections: Vec<String>,
+    /// Always listed above the other favorites
+    #[serde(default)]
+    pub pinned: bool,
+    /// What the character does for its group, for characters saved from a party
+    #[serde(default)]
+    pub role: Option<Role>,
+    /// The plain sentence OCs were saved as before character sheets, see `migrate`
+    #[serde(default, rename = "text", skip_serializing)]
+    legacy_text: String,
+}
+
+impl SavedOC {
+    /// A freshly saved character, stamped with the current time
+    pub fn new(id: CharacterId, sheet: CharacterSheet) -> Self {
+        Self {
+            id,
+            sheet,
+            created_at: chrono::Utc::now().timestamp_millis() as u64,
+            seed: None,
+            traits: vec![],
+            pack_versions: BTreeMap::new(),
+            roll_settings: None,
+            palette: None,
+            tags: vec![],
+            collections: vec![],
+            pinned: false,
+            role: None,
+            legacy_text: String::new(),
+        }
+    }
+
+    /// Move the sentence from saves made before character sheets into the description
+    ///
+    /// Returns whether anything changed so the caller knows to write the config back
+    pub fn migrate(&mut self) -> bool {
+        if self.legacy_text.is_empty() {
+            return false;
+        }
+
+        let text = std::mem::take(&mut self.legacy_text);
+        if self.sheet.description.is_empty() {
+            self.sheet.description = text;
+        }
+        true
+    }
+
+    /// Seeds to rebuild this OC from, one per trait or a single shared one for older saves
+    pub fn seeds(&self) -> Vec<u64> {
+        if self.traits.is_empty() {
+            self.seed.into_iter().collect()
+        } else {
+            self.traits.iter().map(|t| t.seed).collect()
+        }
+    }
+}
+
+/// Settings besides the packs that change what a seed rolls
+///
+/// Kept with each saved OC so rebuilding it can say which of them are different now.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct RollSettings {
+    /// Entries above the rating are left out of the pools, so the same seed lands elsewhere
+    pub content_rating: ContentRating,
+    /// Chance in percent that the species turns out a hybrid
+    pub hybrid_chance: u32,
+    /// How hybrids without a pack name get named
+    pub hybrid_naming: HybridNaming,
+    /// Made-up names instead of names from the packs
+    pub generated_names: bool,
+}
+
+impl RollSettings {
+    fn of(config: &AppConfig) -> Self {
+        Self {
+            content_rating: config.content_rating,
+            hybrid_chance: config.hybrid_chance,
+            hybrid_naming: config.hybrid_naming,
+            generated_names: config.generated_names,
+        }
+    }
+
+    /// A notice for each setting that's different in `current`
+    fn changes(&self, current: &RollSettings) -> Vec<String> {
+        let mut changes = vec![];
+        if self.content_rating != current.content_rating {
+            changes.push(fl!("seed-rating-changed"));
+        }
+        if self.hybrid_chance != current.hybrid_chance || self.hybrid_naming != current.hybrid_naming {
+            changes.push(fl!("seed-hybrids-changed"));
+        }
+        if self.generated_names != current.generated_names {
+            changes.push(fl!("seed-names-changed"));
+        }
+        changes
+    }
+}
+
+/// A deleted character, kept around until it's restored or its retention runs out
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrashedOC {
+    pub character: SavedOC,
+    pub deleted_at: u64,
+    /// Relationships the character had, put back on restore if the other side is still around
+    #[serde(default)]
+    pub relationships: Vec<Relationship>,
 }
 
 // impl Default for Message {
@@ -34,25 +314,203 @@ pub struct SavedOC {
 //     }
 // }
 
+/// A drawn avatar and what it was drawn from
+struct CachedAvatar {
+    traits: Vec<OcTrait>,
+    palette: Option<Palette>,
+    handle: widget::svg::Handle,
+}
+
 /// State for the OC Generator page
 pub struct OcGeneratorPage {
     // Here is where state lives
-    pub oc_text: Option<String>,
+    pub oc: Option<GeneratedOC>,
     pub saved_characters: Vec<SavedOC>,
     pub is_loaded: bool,
+    /// App settings, kept in sync by the app through `Message::UpdateConfig`
+    pub config: AppConfig,
+    /// Every pack that loaded, enabled or not
+    pub packs: Vec<GeneratorPack>,
+    /// Packs that failed to load so we can tell the user instead of failing silently
+    pub pack_errors: Vec<PackError>,
+    /// Word pools merged from the enabled packs
+    pub pools: WordPools,
+    /// Avatars of the saved characters, so the list doesn't draw them again on every view
+    avatars: HashMap<CharacterId, CachedAvatar>,
+    /// Hands out seeds for new OCs so nothing on this page touches the global RNG
+    rng: fastrand::Rng,
+    /// Text typed into the "generate from code" input
+    pub seed_input: String,
+    /// Feedback about the last seed code, like a typo or packs that changed since saving
+    pub seed_notice: Option<String>,
+    /// Saved character whose details are shown instead of the favorites list
+    pub selected_character: Option<CharacterId>,
+    /// Raw edit form text while the selected character is being edited
+    pub sheet_draft: Option<HashMap<SheetField, String>>,
+    /// Saved characters above the content rating that the user chose to show anyway
+    pub revealed_characters: HashSet<CharacterId>,
+    /// Text typed into the favorites search bar
+    pub search_query: String,
+    pub sort_order: SortOrder,
+    /// Dropdown labels for `SortOrder::ALL`, the dropdown borrows them from the page
+    sort_labels: Vec<String>,
+    /// Only show favorites of this species
+    pub species_filter: Option<String>,
+    /// Only show favorites with this tag
+    pub tag_filter: Option<String>,
+    /// Tags of the selected character while it's being edited, comma separated
+    pub tags_draft: String,
+    /// Named groups of saved characters, like a campaign or the commission queue
+    pub collections: Vec<String>,
+    /// Collection the favorites list is showing, `None` for all characters
+    pub selected_collection: Option<String>,
+    /// Dropdown labels, "all characters" followed by `collections`
+    collection_labels: Vec<String>,
+    /// Name typed in for a new collection
+    pub new_collection_input: String,
+    /// Deleted characters, newest last
+    pub trash: Vec<TrashedOC>,
+    /// Show the trash instead of the favorites list
+    pub show_trash: bool,
+    /// Dropdown labels for `TRASH_RETENTION_DAYS`
+    retention_labels: Vec<String>,
+    /// Popups like "Character deleted, Undo"
+    pub toasts: widget::Toasts<Message>,
+    /// Everything generated this session (or longer, with `keep_history`)
+    pub history: History,
+    /// Characters ticked for export, everything gets exported when this is empty
+    pub export_selection: HashSet<CharacterId>,
+    /// Result of the last import, or why it failed
+    pub import_summary: Option<Result<ImportSummary, String>>,
+    /// OCs from the last batch, the grid is hidden while this is empty
+    pub batch: Vec<Candidate>,
+    /// Indices of candidates ticked for saving together
+    pub batch_selection: HashSet<usize>,
+    /// Dropdown labels for `batch::SIZES`
+    batch_size_labels: Vec<String>,
+    /// The last generated party, the party panel is hidden while this is `None`
+    pub party: Option<Party>,
+    /// Collection name typed in for saving the party
+    pub party_name_input: String,
+    /// Dropdown labels for `party::SIZES`
+    party_size_labels: Vec<String>,
+    /// Show the list of liked and disliked entries
+    pub show_weights: bool,
+    /// Like bias while the slider is being dragged, saved to the config once it's let go
+    like_bias_dragged: Option<u32>,
+    /// Name ideas from the name generator, on their own
+    pub name_suggestions: Vec<String>,
+    /// Index into `names::FLAVORS` for the name ideas
+    pub name_flavor: usize,
+    /// Dropdown labels for `names::FLAVORS`
+    name_flavor_labels: Vec<String>,
+    /// Format the copy buttons put on the clipboard
+    pub share_format: ShareFormat,
+    /// Dropdown labels for `ShareFormat::ALL`
+    share_format_labels: Vec<String>,
+    /// Dropdown labels for `hybrid::CHANCES`
+    hybrid_chance_labels: Vec<String>,
+    /// Dropdown labels for `HYBRID_NAMINGS`
+    hybrid_naming_labels: Vec<String>,
+    /// Friendships, rivalries and families between saved characters
+    pub relationships: Vec<Relationship>,
+    /// Show the relationship graph instead of the favorites list
+    pub show_graph: bool,
+    /// The "add relationship" row on the detail view
+    pub relationship_draft: RelationshipDraft,
+    /// Characters the selected one can get a relationship with, and their dropdown labels
+    relationship_targets: Vec<CharacterId>,
+    relationship_target_labels: Vec<String>,
+    /// Dropdown labels for `RelationshipKind::PRESETS` and a custom kind
+    relationship_kind_labels: Vec<String>,
 }
 
 impl Default for OcGeneratorPage {
     fn default() -> Self {
         Self {
-            oc_text: None,
+            oc: None,
             saved_characters: vec![],
             is_loaded: false,
+            config: AppConfig::default(),
+            packs: vec![],
+            pack_errors: vec![],
+            pools: WordPools::default(),
+            avatars: HashMap::new(),
+            rng: fastrand::Rng::new(),
+            seed_input: String::new(),
+            seed_notice: None,
+            selected_character: None,
+            sheet_draft: None,
+            revealed_characters: HashSet::new(),
+            search_query: String::new(),
+            sort_order: SortOrder::default(),
+            sort_labels: SortOrder::ALL.iter().map(|order| order.label()).collect(),
+            species_filter: None,
+            tag_filter: None,
+            tags_draft: String::new(),
+            collections: vec![],
+            selected_collection: None,
+            collection_labels: vec![fl!("all-characters")],
+            new_collection_input: String::new(),
+            trash: vec![],
+            show_trash: false,
+            retention_labels: TRASH_RETENTION_DAYS
+                .iter()
+                .map(|&days| fl!("trash-retention", days = days))
+                .collect(),
+            toasts: widget::Toasts::new(Message::CloseToast),
+            history: History::default(),
+            export_selection: HashSet::new(),
+            import_summary: None,
+            batch: vec![],
+            batch_selection: HashSet::new(),
+            batch_size_labels: batch::SIZES
+                .iter()
+                .map(|&size| fl!("batch-size", count = size))
+                .collect(),
+            party: None,
+            party_name_input: String::new(),
+            party_size_labels: party::SIZES
+                .iter()
+                .map(|&size| fl!("party-size", count = size))
+                .collect(),
+            show_weights: false,
+            like_bias_dragged: None,
+            name_suggestions: vec![],
+            name_flavor: 0,
+            name_flavor_labels: names::FLAVORS.iter().map(|flavor| names::flavor_label(flavor)).collect(),
+            share_format: ShareFormat::default(),
+            share_format_labels: ShareFormat::ALL.iter().map(|format| format.label()).collect(),
+            hybrid_chance_labels: hybrid::CHANCES
+                .iter()
+                .map(|&chance| match chance {
+                    0 => fl!("hybrids-off"),
+                    chance => fl!("hybrid-chance", percent = chance),
+                })
+                .collect(),
+            hybrid_naming_labels: vec![fl!("hybrid-naming-portmanteau"), fl!("hybrid-naming-descriptive")],
+            relationships: vec![],
+            show_graph: false,
+            relationship_draft: RelationshipDraft::default(),
+            relationship_targets: vec![],
+            relationship_target_labels: vec![],
+            relationship_kind_labels: RelationshipKind::PRESETS
+                .iter()
+                .map(RelationshipKind::label)
+                .chain(std::iter::once(fl!("relationship-custom")))
+                .collect(),
         }
     }
 }
 
 impl OcGeneratorPage {
+    pub fn new(config: AppConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
     /// Create the view for this page
     pub fn view(&'_ self) -> Element<'_, Message> {
         // Build the UI here!
@@ -68,7 +526,7 @@ impl OcGeneratorPage {
             space_xxs, space_s, space_m, ..
         } = theme::active().cosmic().spacing;
 
-        let save_button = if self.oc_text.is_some() { 
+        let save_button = if self.oc.is_some() { 
             widget::button::standard(fl!("save-button"))
             .on_press(Message::SaveButtonClicked)
         } else { 
@@ -76,28 +534,104 @@ impl OcGeneratorPage {
         };
         let save_button = save_button
             .class(theme::Button::Suggested);
+
+        let like_button = widget::button::icon(icon::from_name("face-smile-symbolic"))
+            .tooltip(fl!("like-button"));
+        let dislike_button = widget::button::icon(icon::from_name("face-sad-symbolic"))
+            .tooltip(fl!("dislike-button"));
+        let (like_button, dislike_button) = if self.oc.is_some() {
+            (
+                like_button.on_press(Message::RateOc(true)),
+                dislike_button.on_press(Message::RateOc(false)),
+            )
+        } else {
+            (like_button, dislike_button)
+        };
         
 
+        let back_button = widget::button::icon(icon::from_name("go-previous-symbolic"));
+        let back_button = if self.history.can_go_back() {
+            back_button.on_press(Message::HistoryBack)
+        } else {
+            back_button
+        };
+        let forward_button = widget::button::icon(icon::from_name("go-next-symbolic"));
+        let forward_button = if self.history.can_go_forward() {
+            forward_button.on_press(Message::HistoryForward)
+        } else {
+            forward_button
+        };
+
         let button_row = row()
             .spacing(space_m)
             .align_y(Vertical::Center)
             .push(widget::horizontal_space()) // Centers the buttons
+            .push(back_button)
             .push(
                 widget::button::standard(fl!("generate-button"))
                     .on_press(Message::GenerateButtonClicked)
             )
             .push(save_button)
+            .push(like_button)
+            .push(dislike_button)
+            .push(forward_button)
+            .push(widget::dropdown(
+                &self.batch_size_labels,
+                batch::SIZES
+                    .iter()
+                    .position(|&size| size == self.config.batch_size),
+                Message::BatchSizeChanged,
+            ))
+            .push(
+                widget::button::standard(fl!("generate-batch-button"))
+                    .on_press(Message::GenerateBatchClicked),
+            )
+            .push(widget::dropdown(
+                &self.party_size_labels,
+                party::SIZES
+                    .iter()
+                    .position(|&size| size == self.config.party_size),
+                Message::PartySizeChanged,
+            ))
+            .push(
+                widget::button::standard(fl!("generate-party-button"))
+                    .on_press(Message::GeneratePartyClicked),
+            )
             .push(widget::horizontal_space()); // Centers the buttons
 
         let content_section = column::column()
             .spacing(space_m)
             .push(
-                text::title4(self.oc_text.clone().unwrap_or_default())
+                text::title4(
+                    self.oc
+                        .as_ref()
+                        .map(|oc| oc.text(self.pools.grammar()))
+                        .unwrap_or_default(),
+                )
                     .apply(widget::container)
                     .width(Length::Fill)
                     .align_x(Horizontal::Center),
             )
+            .push_maybe(self.oc.as_ref().map(|oc| {
+                text::caption(fl!("seed-code", code = oc.seed_code()))
+                    .apply(widget::container)
+                    .width(Length::Fill)
+                    .align_x(Horizontal::Center)
+            }))
+            .push_maybe(self.oc.as_ref().map(|_| self.share_row()))
+            .push_maybe(self.oc.as_ref().and_then(|oc| {
+                let name = oc.get(TraitSlot::Name).map(|t| t.text.as_str()).unwrap_or_default();
+                oc.palette.as_ref().map(|palette| self.palette_row(palette, name))
+            }))
+            .push_maybe(self.oc.as_ref().map(|oc| self.trait_lock_row(oc)))
             .push(button_row)
+            .push_maybe((!self.batch.is_empty()).then(|| self.batch_grid()))
+            .push_maybe(self.party.as_ref().map(|party| self.party_panel(party)))
+            .push(self.history_strip())
+            .push(self.seed_section())
+            .push(self.pack_section())
+            .push(self.weights_section())
+            .push(self.names_section())
             .apply(widget::container)
             .width(Length::Fill)
             .height(Length::Shrink)
@@ -105,15 +639,17 @@ impl OcGeneratorPage {
             .align_y(Vertical::Center);
 
         // Build widget from sections
-        column::column()
+        let page = column::column()
             .spacing(space_s) // Use consistent small spacing
             .push(self.view_header(space_xxs.into()))
+            .push_maybe(self.pack_errors_section())
             .push(content_section)
             .push(self.favorite_section())
             .padding(space_xxs)
             .width(Length::Fill)
-            .height(Length::Fill)
-            .into()
+            .height(Length::Fill);
+
+        widget::toaster(&self.toasts, page)
     }
 
     fn view_header(&self, space_xxs: f32) -> Element<'_, Message> {
@@ -126,7 +662,399 @@ impl OcGeneratorPage {
             .into()
     }
 
+    /// Copy the current OC, in the format picked here (saved cards copy in the same format)
+    fn share_row(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+
+        row()
+            .spacing(space_xxs)
+            .align_y(Vertical::Center)
+            .push(widget::horizontal_space())
+            .push(widget::dropdown(
+                &self.share_format_labels,
+                ShareFormat::ALL
+                    .iter()
+                    .position(|&format| format == self.share_format),
+                Message::ShareFormatChanged,
+            ))
+            .push(
+                widget::button::standard(fl!("copy-button"))
+                    .leading_icon(icon::from_name("edit-copy-symbolic"))
+                    .on_press(Message::CopyCurrentOc),
+            )
+            .push(widget::horizontal_space())
+            .into()
+    }
+
+    /// One lock toggle per trait so "Generate!" only rerolls the rest
+    fn trait_lock_row(&self, oc: &GeneratedOC) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_m, ..
+        } = theme::active().cosmic().spacing;
+
+        let mut locks = vec![];
+        for oc_trait in &oc.traits {
+            let lock_icon = if oc.is_locked(oc_trait.slot) {
+                "changes-prevent-symbolic"
+            } else {
+                "changes-allow-symbolic"
+            };
+
+            locks.push(
+                row()
+                    .spacing(space_xxs)
+                    .align_y(Vertical::Center)
+                    .push(
+                        widget::button::icon(icon::from_name(lock_icon))
+                            .on_press(Message::ToggleLock(oc_trait.slot)),
+                    )
+                    .push(text::body(format!(
+                        "{}: {}",
+                        oc_trait.slot.label(),
+                        oc_trait.text
+                    )))
+                    .into(),
+            );
+        }
+
+        widget::flex_row(locks)
+            .row_spacing(space_xxs)
+            .column_spacing(space_m)
+            .apply(widget::container)
+            .width(Length::Fill)
+            .align_x(Horizontal::Center)
+            .into()
+    }
+
+    /// Every OC in the history, so any earlier roll is one click away
+    fn history_strip(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let entries = self
+            .history
+            .iter()
+            .enumerate()
+            .map(|(index, oc)| {
+                let label = oc
+                    .get(TraitSlot::Name)
+                    .map(|name| name.text.clone())
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| (index + 1).to_string());
+                let class = if index == self.history.position() {
+                    theme::Button::Suggested
+                } else {
+                    theme::Button::Standard
+                };
+
+                widget::button::text(label)
+                    .on_press(Message::HistoryJump(index))
+                    .class(class)
+                    .into()
+            })
+            .collect::<Vec<Element<'_, Message>>>();
+
+        row()
+            .spacing(space_s)
+            .align_y(Vertical::Center)
+            .push(text::caption(fl!("history")))
+            .push(
+                widget::flex_row(entries)
+                    .row_spacing(space_xxs)
+                    .column_spacing(space_xxs)
+                    .width(Length::Fill),
+            )
+            .push(
+                widget::checkbox(fl!("keep-history"), self.config.keep_history)
+                    .on_toggle(Message::KeepHistoryToggled),
+            )
+            .into()
+    }
+
+    /// Swatches for a palette, with buttons to export it
+    fn palette_row(&self, palette: &Palette, name: &str) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_s, .. } = theme::active().cosmic().spacing;
+
+        let export_button = |label, format| {
+            widget::button::text(label).on_press(Message::ExportPalette(
+                palette.clone(),
+                name.to_string(),
+                format,
+            ))
+        };
+
+        row()
+            .spacing(space_s)
+            .align_y(Vertical::Center)
+            .push(widget::horizontal_space())
+            .push(swatches(palette))
+            .push(text::caption(palette.harmony.label()))
+            .push(export_button(fl!("export-hex-button"), PaletteFormat::HexList))
+            .push(export_button(fl!("export-gpl-button"), PaletteFormat::Gimp))
+            .push(widget::horizontal_space())
+            .into()
+    }
+
+    /// Input for rebuilding an OC someone shared as a seed code
+    fn seed_section(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let input_row = row()
+            .spacing(space_s)
+            .align_y(Vertical::Center)
+            .push(widget::horizontal_space())
+            .push(
+                widget::text_input(fl!("seed-placeholder"), &self.seed_input)
+                    .on_input(Message::SeedInputChanged)
+                    .width(Length::Fixed(160.0)),
+            )
+            .push(
+                widget::button::standard(fl!("generate-from-code-button"))
+                    .on_press(Message::GenerateFromCodeClicked),
+            )
+            .push(widget::horizontal_space());
+
+        column::column()
+            .spacing(space_xxs)
+            .push(input_row)
+            .push_maybe(self.seed_notice.as_deref().map(|notice| {
+                text::caption(notice)
+                    .apply(widget::container)
+                    .width(Length::Fill)
+                    .align_x(Horizontal::Center)
+            }))
+            .into()
+    }
+
+    /// Checkboxes for switching generator packs on and off
+    fn pack_section(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_s, .. } = theme::active().cosmic().spacing;
+
+        let mut pack_row = row()
+            .spacing(space_s)
+            .align_y(Vertical::Center)
+            .push(widget::horizontal_space())
+            .push(text::body(fl!("packs")));
+
+        for pack in &self.packs {
+            let enabled = !self.config.disabled_packs.contains(&pack.id);
+            let pack_id = pack.id.clone();
+            pack_row = pack_row.push(
+                widget::checkbox(pack.name.clone(), enabled)
+                    .on_toggle(move |enabled| Message::PackToggled(pack_id.clone(), enabled)),
+            );
+        }
+
+        pack_row
+            .push(
+                widget::checkbox(
+                    fl!("mature-content"),
+                    self.config.content_rating == ContentRating::Mature,
+                )
+                .on_toggle(Message::MatureContentToggled),
+            )
+            .push(widget::dropdown(
+                &self.hybrid_chance_labels,
+                hybrid::CHANCES
+                    .iter()
+                    .position(|&chance| chance == self.config.hybrid_chance),
+                Message::HybridChanceChanged,
+            ))
+            .push_maybe((self.config.hybrid_chance > 0).then(|| {
+                widget::dropdown(
+                    &self.hybrid_naming_labels,
+                    HYBRID_NAMINGS
+                        .iter()
+                        .position(|&naming| naming == self.config.hybrid_naming),
+                    Message::HybridNamingChanged,
+                )
+            }))
+            .push(
+                widget::checkbox(fl!("generated-names"), self.config.generated_names)
+                    .on_toggle(Message::GeneratedNamesToggled),
+            )
+            .push(widget::horizontal_space())
+            .into()
+    }
+
+    /// The "surprise me" to "more like my favorites" slider, and what's been liked so far
+    fn weights_section(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let weights = &self.config.entry_weights;
+        let reset_button = widget::button::standard(fl!("reset-weights-button"));
+        let reset_button = if weights.is_empty() {
+            reset_button
+        } else {
+            reset_button.on_press(Message::ResetWeights)
+        };
+
+        let bias_row = row()
+            .spacing(space_s)
+            .align_y(Vertical::Center)
+            .push(widget::horizontal_space())
+            .push(text::body(fl!("surprise-me")))
+            .push(
+                widget::slider(
+                    0..=100,
+                    self.like_bias_dragged.unwrap_or(self.config.like_bias),
+                    Message::LikeBiasChanged,
+                )
+                .on_release(Message::LikeBiasReleased)
+                .width(Length::Fixed(200.0)),
+            )
+            .push(text::body(fl!("more-like-favorites")))
+            .push(
+                widget::button::standard(fl!("weights-button", count = weights.len()))
+                    .on_press(Message::ShowWeights(!self.show_weights)),
+            )
+            .push(reset_button)
+            .push(widget::horizontal_space());
+
+        let weight_list = self.show_weights.then(|| {
+            let mut sorted: Vec<(&String, &u32)> = weights.iter().collect();
+            sorted.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+
+            let mut list = column::column().spacing(space_xxs);
+            for (key, weight) in sorted {
+                list = list.push(
+                    row()
+                        .spacing(space_s)
+                        .push(text::body(self.weight_label(key)).width(Length::Fixed(280.0)))
+                        .push(text::body(fl!(
+                            "weight",
+                            weight = format!("{:.2}", f64::from(*weight) / f64::from(weights::NEUTRAL))
+                        ))),
+                );
+            }
+            if weights.is_empty() {
+                list = list.push(text::caption(fl!("no-weights")));
+            }
+            list.apply(widget::container)
+                .width(Length::Fill)
+                .align_x(Horizontal::Center)
+        });
+
+        column::column()
+            .spacing(space_xxs)
+            .push(bias_row)
+            .push_maybe(weight_list)
+            .into()
+    }
+
+    /// The name generator on its own, for when only a name is missing
+    fn names_section(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let suggestions = self
+            .name_suggestions
+            .iter()
+            .map(|name| {
+                widget::button::text(name.clone())
+                    .tooltip(fl!("copy-name"))
+                    .on_press(Message::CopyName(name.clone()))
+                    .into()
+            })
+            .collect::<Vec<Element<'_, Message>>>();
+
+        column::column()
+            .spacing(space_xxs)
+            .push(
+                row()
+                    .spacing(space_s)
+                    .align_y(Vertical::Center)
+                    .push(widget::horizontal_space())
+                    .push(text::body(fl!("name-ideas")))
+                    .push(widget::dropdown(
+                        &self.name_flavor_labels,
+                        Some(self.name_flavor),
+                        Message::NameFlavorChanged,
+                    ))
+                    .push(
+                        widget::button::standard(fl!("suggest-names-button"))
+                            .on_press(Message::SuggestNames),
+                    )
+                    .push(widget::horizontal_space()),
+            )
+            .push_maybe((!suggestions.is_empty()).then(|| {
+                widget::flex_row(suggestions)
+                    .row_spacing(space_xxs)
+                    .column_spacing(space_xxs)
+                    .apply(widget::container)
+                    .width(Length::Fill)
+                    .align_x(Horizontal::Center)
+            }))
+            .into()
+    }
+
+    /// "Species: fox" for a weight key like `species/fox`, the bare key if its pack is off
+    fn weight_label(&self, key: &str) -> String {
+        let Some((category, id)) = key.split_once('/') else {
+            return key.to_string();
+        };
+        let slot = TraitSlot::ALL.iter().find(|slot| slot.category() == category);
+        let entry = self
+            .pools
+            .entries(category)
+            .iter()
+            .find(|entry| entry.id == id);
+
+        match (slot, entry) {
+            (Some(slot), Some(entry)) => format!("{}: {}", slot.label(), entry.label()),
+            (Some(slot), None) => format!("{}: {id}", slot.label()),
+            _ => key.to_string(),
+        }
+    }
+
+    /// Lists packs that failed to load, if any
+    fn pack_errors_section(&self) -> Option<Element<'_, Message>> {
+        if self.pack_errors.is_empty() {
+            return None;
+        }
+
+        let cosmic_theme::Spacing { space_xxs, .. } = theme::active().cosmic().spacing;
+
+        let mut errors = column::column()
+            .spacing(space_xxs)
+            .push(text::heading(fl!("pack-errors")));
+        for error in &self.pack_errors {
+            errors = errors.push(
+                row()
+                    .spacing(space_xxs)
+                    .align_y(Vertical::Center)
+                    .push(icon::from_name("dialog-warning-symbolic").size(16).icon())
+                    .push(text::body(error.to_string())),
+            );
+        }
+
+        Some(
+            errors
+                .apply(widget::container)
+                .width(Length::Fill)
+                .padding(space_xxs)
+                .into(),
+        )
+    }
+
     fn favorite_section(&self) -> Element<'_, Message> {
+        if self.show_trash {
+            return self.trash_view();
+        }
+        if self.show_graph {
+            return self.graph_view();
+        }
+
+        if let Some(saved) = self.selected_character.and_then(|id| self.character(id)) {
+            return self.character_detail(saved);
+        }
+
         column::column()
             .push(
                 widget::text::title2(fl!("favorites"))
@@ -134,6 +1062,10 @@ impl OcGeneratorPage {
                     .width(Length::Fill)
                     .align_x(Horizontal::Center),
             )
+            .push(self.collection_bar())
+            .push(self.favorite_filters())
+            .push(self.export_bar())
+            .push_maybe(self.import_summary_section())
             .push(
                 self.character_card_list()
                     .apply(widget::container)
@@ -150,6 +1082,230 @@ impl OcGeneratorPage {
             .into()
     }
 
+    /// Switch between collections, make new ones or delete the current one
+    fn collection_bar(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_s, .. } = theme::active().cosmic().spacing;
+
+        let selected = match &self.selected_collection {
+            Some(name) => self.collections.iter().position(|c| c == name).map(|i| i + 1),
+            None => Some(0),
+        };
+
+        let create_button = if self.new_collection_input.trim().is_empty() {
+            widget::button::standard(fl!("new-collection-button"))
+        } else {
+            widget::button::standard(fl!("new-collection-button")).on_press(Message::CreateCollection)
+        };
+
+        row()
+            .spacing(space_s)
+            .align_y(Vertical::Center)
+            .push(widget::dropdown(
+                &self.collection_labels,
+                selected,
+                Message::CollectionSelected,
+            ))
+            .push_maybe(self.selected_collection.is_some().then(|| {
+                widget::button::icon(icon::from_name("edit-delete-symbolic"))
+                    .on_press(Message::DeleteCollection)
+                    .class(theme::Button::Destructive)
+            }))
+            .push(widget::horizontal_space())
+            .push(
+                widget::text_input(fl!("new-collection-placeholder"), &self.new_collection_input)
+                    .on_input(Message::NewCollectionInputChanged)
+                    .width(Length::Fixed(180.0)),
+            )
+            .push(create_button)
+            .push(
+                widget::button::standard(fl!("trash-button", count = self.trash.len()))
+                    .leading_icon(icon::from_name("user-trash-symbolic"))
+                    .on_press(Message::ShowTrash(true)),
+            )
+            .push(
+                widget::button::standard(fl!("relationship-graph-button"))
+                    .on_press(Message::ShowRelationshipGraph(true)),
+            )
+            .into()
+    }
+
+    /// Search bar, sort order and species chips for the favorites list
+    fn favorite_filters(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let selected_sort = SortOrder::ALL.iter().position(|order| *order == self.sort_order);
+        let search_row = row()
+            .spacing(space_s)
+            .align_y(Vertical::Center)
+            .push(
+                widget::search_input(fl!("search-placeholder"), &self.search_query)
+                    .on_input(Message::SearchChanged)
+                    .on_clear(Message::SearchChanged(String::new()))
+                    .width(Length::Fill),
+            )
+            .push(widget::dropdown(&self.sort_labels, selected_sort, Message::SortChanged));
+
+        // Chips only come from characters that are shown, so they can't give away hidden ones
+        let shown = || self.saved_characters.iter().filter(|character| !self.is_hidden(character));
+        let mut species: Vec<&str> = shown()
+            .flat_map(|character| {
+                std::iter::once(&character.sheet.species)
+                    .chain(&character.sheet.parent_species)
+                    .map(String::as_str)
+            })
+            .filter(|species| !species.is_empty())
+            .collect();
+        species.sort_unstable();
+        species.dedup();
+
+        let mut tags: Vec<&str> = shown()
+            .flat_map(|character| character.tags.iter().map(String::as_str))
+            .collect();
+        tags.sort_unstable();
+        tags.dedup();
+
+        let chip = |label: String, selected: bool, message: Message| -> Element<'_, Message> {
+            let class = if selected {
+                theme::Button::Suggested
+            } else {
+                theme::Button::Standard
+            };
+            widget::button::text(label).on_press(message).class(class).into()
+        };
+
+        let species_chips = species.into_iter().map(|species| {
+            chip(
+                species.to_string(),
+                self.species_filter.as_deref() == Some(species),
+                Message::SpeciesFilterToggled(species.to_string()),
+            )
+        });
+        let tag_chips = tags.into_iter().map(|tag| {
+            chip(
+                format!("#{tag}"),
+                self.tag_filter.as_deref() == Some(tag),
+                Message::TagFilterToggled(tag.to_string()),
+            )
+        });
+        let chips = species_chips.chain(tag_chips).collect::<Vec<_>>();
+
+        column::column()
+            .spacing(space_xxs)
+            .push(search_row)
+            .push(widget::flex_row(chips).row_spacing(space_xxs).column_spacing(space_xxs))
+            .padding([space_xxs, 0])
+            .into()
+    }
+
+    /// Export the ticked characters (or all of them) and import from a JSON export
+    fn export_bar(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let label = if self.export_selection.is_empty() {
+            fl!("export-all")
+        } else {
+            fl!("export-selected", count = self.export_selection.len())
+        };
+
+        let mut export_row = row()
+            .spacing(space_xxs)
+            .align_y(Vertical::Center)
+            .push(text::body(label));
+        for format in ExportFormat::ALL {
+            export_row = export_row.push(
+                widget::button::text(format.label()).on_press(Message::ExportCharacters(format)),
+            );
+        }
+
+        row()
+            .spacing(space_s)
+            .align_y(Vertical::Center)
+            .push(export_row)
+            .push(widget::horizontal_space())
+            .push(
+                widget::button::standard(fl!("import-button"))
+                    .leading_icon(icon::from_name("document-open-symbolic"))
+                    .on_press(Message::ImportClicked),
+            )
+            .into()
+    }
+
+    /// What the last import added and skipped
+    fn import_summary_section(&self) -> Option<Element<'_, Message>> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let details = match self.import_summary.as_ref()? {
+            Ok(summary) => column::column()
+                .spacing(space_xxs)
+                .push(text::heading(fl!(
+                    "import-summary",
+                    added = summary.added.len(),
+                    skipped = summary.skipped.len()
+                )))
+                .push_maybe((!summary.added.is_empty()).then(|| {
+                    text::caption(fl!("import-added", names = summary.added.join(", ")))
+                }))
+                .push_maybe((!summary.skipped.is_empty()).then(|| {
+                    text::caption(fl!("import-skipped", names = summary.skipped.join(", ")))
+                })),
+            Err(error) => column::column()
+                .spacing(space_xxs)
+                .push(text::heading(fl!("import-failed")))
+                .push(text::caption(error.as_str())),
+        };
+
+        Some(
+            row()
+                .spacing(space_s)
+                .align_y(Vertical::Center)
+                .push(details.width(Length::Fill))
+                .push(
+                    widget::button::icon(icon::from_name("window-close-symbolic"))
+                        .on_press(Message::DismissImportSummary),
+                )
+                .padding(space_xxs)
+                .into(),
+        )
+    }
+
+    /// Saved characters that pass the search and filters, in the chosen order
+    fn visible_characters(&self) -> Vec<&SavedOC> {
+        let mut visible: Vec<&SavedOC> = self
+            .saved_characters
+            .iter()
+            .filter(|character| {
+                self.selected_collection
+                    .as_ref()
+                    .is_none_or(|collection| character.collections.contains(collection))
+            })
+            .filter(|character| {
+                self.species_filter
+                    .as_ref()
+                    .is_none_or(|species| {
+                        &character.sheet.species == species
+                            || character.sheet.parent_species.contains(species)
+                    })
+            })
+            .filter(|character| {
+                self.tag_filter
+                    .as_ref()
+                    .is_none_or(|tag| character.tags.contains(tag))
+            })
+            .filter(|character| favorites::matches(&self.search_query, character))
+            .collect();
+
+        visible.sort_by(|a, b| self.sort_order.compare(a, b));
+        // Stable, so pinned characters keep the chosen order among themselves
+        visible.sort_by_key(|character| !character.pinned);
+        visible
+    }
+
     fn character_card_list(&self) -> Element<'_, Message> {
         // create a scrollable list of character cards
         let cosmic_theme::Spacing {
@@ -157,9 +1313,18 @@ impl OcGeneratorPage {
             space_xs,
             ..
         } = theme::active().cosmic().spacing;
+        let visible = self.visible_characters();
         let mut list = widget::list::list_column();
-        for (index, _character) in self.saved_characters.iter().enumerate() {
-            list = list.add(self.character_card(index)).spacing(space_xs);
+        for (index, saved) in visible.iter().enumerate() {
+            // Moves stay on their side of the pinned ones
+            let can_move = |neighbor: Option<&&SavedOC>| neighbor.is_some_and(|n| n.pinned == saved.pinned);
+            let moves = (self.sort_order == SortOrder::Manual).then(|| {
+                (
+                    can_move(index.checked_sub(1).and_then(|i| visible.get(i))),
+                    can_move(visible.get(index + 1)),
+                )
+            });
+            list = list.add(self.character_card(saved, moves)).spacing(space_xs);
         }
 
         widget::scrollable(
@@ -169,26 +1334,94 @@ impl OcGeneratorPage {
         )
         .into()
     }
-    fn character_card(&self, index: usize) -> Element<'_, Message> {
+    /// One favorite, with move buttons when `moves` says whether it can go up and down
+    fn character_card<'a>(&'a self, saved: &'a SavedOC, moves: Option<(bool, bool)>) -> Element<'a, Message> {
         let cosmic_theme::Spacing {
             space_xs, ..
         } = theme::active().cosmic().spacing;
 
+        if self.is_hidden(saved) {
+            return self.hidden_character_card(saved.id);
+        }
+
+        let sheet = &saved.sheet;
+        let id = saved.id;
+
+        let pin_tooltip = if saved.pinned {
+            fl!("unpin-button")
+        } else {
+            fl!("pin-button")
+        };
+        let pin_button = widget::button::icon(icon::from_name("view-pin-symbolic"))
+            .tooltip(pin_tooltip)
+            .selected(saved.pinned)
+            .on_press(Message::TogglePin(id));
+
+        let move_buttons = moves.map(|(up, down)| {
+            let up_button = widget::button::icon(icon::from_name("go-up-symbolic"))
+                .tooltip(fl!("move-up-button"));
+            let down_button = widget::button::icon(icon::from_name("go-down-symbolic"))
+                .tooltip(fl!("move-down-button"));
+            column::column()
+                .push(if up { up_button.on_press(Message::MoveCharacter(id, true)) } else { up_button })
+                .push(if down { down_button.on_press(Message::MoveCharacter(id, false)) } else { down_button })
+        });
+
         row::row()
             .spacing(space_xs)
             .align_y(Vertical::Center)
             .padding(space_xs)
+            .push(
+                // Tick for exporting just some characters
+                widget::checkbox("", self.export_selection.contains(&id))
+                    .on_toggle(move |selected| Message::ExportSelectionToggled(id, selected)),
+            )
+            .push_maybe(move_buttons)
+            .push(
+                widget::svg(self.avatar(saved))
+                .width(Length::Fixed(avatar::SIZE))
+                .height(Length::Fixed(avatar::SIZE)),
+            )
             .push(
                 // Character text - consistent styling and proper fill
-                widget::text::body(&self.saved_characters[index].text)
+                column::column()
+                    .push_maybe((!sheet.name.is_empty()).then(|| widget::text::heading(&sheet.name)))
+                    .push(widget::text::body(&sheet.description))
+                    .push(widget::text::caption(fl!(
+                        "saved-on",
+                        date = favorites::created_date(saved)
+                    )))
+                    .push_maybe(saved.palette.as_ref().map(swatches))
                     .width(Length::Fill)
                     .apply(widget::container)
                     .align_y(Vertical::Center),
             )
+            .push(
+                widget::button::icon(icon::from_name("edit-copy-symbolic"))
+                    .tooltip(fl!("copy-as", format = self.share_format.label()))
+                    .on_press(Message::CopyCharacter(id))
+                    .apply(widget::container)
+                    .align_y(Vertical::Center),
+            )
+            .push(pin_button.apply(widget::container).align_y(Vertical::Center))
+            .push(
+                widget::button::icon(icon::from_name("go-next-symbolic"))
+                    .tooltip(fl!("details-button"))
+                    .on_press(Message::OpenCharacter(saved.id))
+                    .apply(widget::container)
+                    .align_y(Vertical::Center),
+            )
+            .push_maybe((!saved.seeds().is_empty()).then(|| {
+                // Rebuild button, only for OCs that remember their seed
+                widget::button::icon(icon::from_name("view-refresh-symbolic"))
+                    .on_press(Message::RebuildCharacter(saved.id))
+                    .apply(widget::container)
+                    .align_y(Vertical::Center)
+            }))
             .push(
                 // Delete button - consistent positioning
                 widget::button::icon(icon::from_name("user-trash-symbolic"))
-                    .on_press(Message::DeleteCharacter(index))
+                    .on_press(Message::DeleteCharacter(saved.id))
                     .class(theme::Button::Destructive)
                     .apply(widget::container)
                     .align_y(Vertical::Center),
@@ -199,39 +1432,607 @@ impl OcGeneratorPage {
             .into()
     }
 
-    /// Handle messages for this page
-    pub fn update(&mut self, message: Message) -> cosmic::Task<cosmic::Action<Message>> {
-        match message {
-            Message::LoadData => {
-                if !self.is_loaded {
-                    self.load_characters().unwrap_or_else(|e| {
-                        eprintln!("Unexpected error loading characters: {:?}", e);
+    /// Stand-in card for a saved character above the content rating
+    fn hidden_character_card(&self, id: CharacterId) -> Element<'_, Message> {
+        let cosmic_theme::Spacing { space_xs, .. } = theme::active().cosmic().spacing;
+
+        row::row()
+            .spacing(space_xs)
+            .align_y(Vertical::Center)
+            .padding(space_xs)
+            .push(icon::from_name("view-conceal-symbolic").size(16).icon())
+            .push(widget::text::body(fl!("hidden-character")).width(Length::Fill))
+            .push(
+                widget::button::standard(fl!("reveal-button"))
+                    .on_press(Message::RevealCharacter(id)),
+            )
+            .push(
+                widget::button::icon(icon::from_name("user-trash-symbolic"))
+                    .on_press(Message::DeleteCharacter(id))
+                    .class(theme::Button::Destructive),
+            )
+            .apply(widget::container)
+            .width(Length::Fill)
+            .into()
+    }
+
+    /// Handle messages for this page
+    pub fn update(&mut self, message: Message) -> cosmic::Task<cosmic::Action<Message>> {
+        let task = self.handle_message(message);
+        // Any message may have changed characters, redrawing just the avatars that did
+        self.refresh_avatars();
+        task
+    }
+
+    fn handle_message(&mut self, message: Message) -> cosmic::Task<cosmic::Action<Message>> {
+        match message {
+            Message::LoadData => {
+                if !self.is_loaded {
+                    self.load_packs();
+                    self.load_characters().unwrap_or_else(|e| {
+                        eprintln!("Unexpected error loading characters: {:?}", e);
                     });
+                    if self.config.keep_history {
+                        self.load_history();
+                    }
+                    self.is_loaded = true;
                 }
             }
-            Message::GenerateButtonClicked => self.oc_text = Some(self.generate()),
+            Message::GenerateButtonClicked => {
+                self.reroll_unlocked();
+                self.seed_notice = None;
+            }
             Message::SaveButtonClicked => {
-                if let Some(oc) = &self.oc_text {
-                    let new_saved = SavedOC {
-                        text: oc.clone(),
-                        created_at: chrono::Utc::now().timestamp_millis() as u64,
-                    };
+                if let Some(oc) = self.oc.clone() {
+                    let new_saved = self.saved_oc(&oc);
                     self.saved_characters.push(new_saved);
                     if let Err(e) = self.save_characters() {
                         eprintln!("Error saving characters: {:?}", e);
                     } else {
-                        //Todo: show message to user
-                        println!("Saved characters successfully!");
+                        return self
+                            .toasts
+                            .push(widget::toaster::Toast::new(fl!("character-saved")))
+                            .map(cosmic::Action::App);
                     }
                 }
             }
-            Message::DeleteCharacter(index) => {
-                if let Err(e) = self.delete_character_at_index(index) {
+            Message::DeleteCharacter(id) => {
+                if self.selected_character == Some(id) {
+                    self.selected_character = None;
+                    self.sheet_draft = None;
+                }
+                self.revealed_characters.remove(&id);
+                self.export_selection.remove(&id);
+                if let Err(e) = self.delete_character(id) {
                     eprintln!("Error saving characters: {:?}", e);
                     let _ = self.load_characters();
                 } else {
-                    // Todo: show message to user
-                    print!("Character removed");
+                    return self
+                        .toasts
+                        .push(
+                            widget::toaster::Toast::new(fl!("character-deleted"))
+                                .action(fl!("undo-button"), move |_| Message::RestoreCharacter(id)),
+                        )
+                        .map(cosmic::Action::App);
+                }
+            }
+            Message::HybridChanceChanged(index) => {
+                if let Some(&chance) = hybrid::CHANCES.get(index) {
+                    if let Err(e) = self.set_hybrid_chance(chance) {
+                        eprintln!("Error saving hybrid chance: {:?}", e);
+                    }
+                }
+            }
+            Message::HybridNamingChanged(index) => {
+                if let Some(&naming) = HYBRID_NAMINGS.get(index) {
+                    if let Err(e) = self.set_hybrid_naming(naming) {
+                        eprintln!("Error saving hybrid naming: {:?}", e);
+                    }
+                }
+            }
+            Message::GeneratedNamesToggled(generated) => {
+                if let Err(e) = self.set_generated_names(generated) {
+                    eprintln!("Error saving name setting: {:?}", e);
+                }
+            }
+            Message::NameFlavorChanged(index) => {
+                if index < names::FLAVORS.len() {
+                    self.name_flavor = index;
+                }
+            }
+            Message::SuggestNames => {
+                let flavor = names::FLAVORS[self.name_flavor];
+                self.name_suggestions = (0..names::SUGGESTIONS)
+                    .map(|_| names::generate(flavor, &mut self.rng))
+                    .collect();
+            }
+            Message::CopyName(name) => {
+                let toast = self
+                    .toasts
+                    .push(widget::toaster::Toast::new(fl!("copied-name", name = name.clone())))
+                    .map(cosmic::Action::App);
+                return cosmic::Task::batch([cosmic::iced::clipboard::write(name), toast]);
+            }
+            Message::PackToggled(pack_id, enabled) => {
+                if let Err(e) = self.set_pack_enabled(pack_id, enabled) {
+                    eprintln!("Error saving pack selection: {:?}", e);
+                }
+            }
+            Message::UpdateConfig(config) => {
+                self.config = config;
+                self.rebuild_pools();
+            }
+            Message::SeedInputChanged(input) => self.seed_input = input,
+            Message::GenerateFromCodeClicked => {
+                let seeds = seed::decode_all(&self.seed_input)
+                    .filter(|seeds| (1..=TraitSlot::ALL.len()).contains(&seeds.len()));
+                match seeds {
+                    Some(seeds) => {
+                        let oc = self.generate_from_seeds(&seeds);
+                        self.show_oc(oc);
+                        self.seed_notice = None;
+                    }
+                    None => self.seed_notice = Some(fl!("seed-invalid")),
+                }
+            }
+            Message::RebuildCharacter(id) => {
+                if let Some(saved) = self.character(id) {
+                    let seeds = saved.seeds();
+                    let mut changes = vec![];
+                    if saved.pack_versions != self.pack_versions() {
+                        changes.push(fl!("seed-packs-changed"));
+                    }
+                    if let Some(settings) = &saved.roll_settings {
+                        changes.extend(settings.changes(&RollSettings::of(&self.config)));
+                    }
+
+                    if !seeds.is_empty() {
+                        let oc = self.generate_from_seeds(&seeds);
+                        self.show_oc(oc);
+                        self.seed_notice = (!changes.is_empty()).then(|| changes.join("\n"));
+                    }
+                }
+            }
+            Message::ToggleLock(slot) => {
+                if let Some(oc) = &mut self.oc {
+                    oc.toggle_lock(slot);
+                    self.history.replace_current(oc.clone());
+                }
+            }
+            Message::HistoryBack => {
+                if let Some(oc) = self.history.back() {
+                    self.oc = Some(oc.clone());
+                }
+            }
+            Message::HistoryForward => {
+                if let Some(oc) = self.history.forward() {
+                    self.oc = Some(oc.clone());
+                }
+            }
+            Message::HistoryJump(index) => {
+                if let Some(oc) = self.history.jump(index) {
+                    self.oc = Some(oc.clone());
+                }
+            }
+            Message::ExportSelectionToggled(id, selected) => {
+                if selected {
+                    self.export_selection.insert(id);
+                } else {
+                    self.export_selection.remove(&id);
+                }
+            }
+            Message::ExportCharacters(format) => {
+                let characters: Vec<&SavedOC> = self
+                    .saved_characters
+                    .iter()
+                    .filter(|c| self.export_selection.is_empty() || self.export_selection.contains(&c.id))
+                    .collect();
+
+                match export::export(&characters, format) {
+                    Ok(contents) => {
+                        let file_name = format!("{}.{}", fl!("export-file-name"), format.extension());
+                        return save_file(file_name, move || Ok(contents.into_bytes()));
+                    }
+                    Err(e) => {
+                        eprintln!("Error exporting characters: {e}");
+                        return self
+                            .toasts
+                            .push(widget::toaster::Toast::new(fl!("export-failed", error = e)))
+                            .map(cosmic::Action::App);
+                    }
+                }
+            }
+            Message::ImportClicked => return open_file(),
+            Message::ImportLoaded(result) => match result {
+                Ok(Some(source)) => {
+                    self.import_summary = Some(
+                        export::import_json(&source).map(|characters| self.import_characters(characters)),
+                    );
+                }
+                Ok(None) => {}
+                Err(e) => self.import_summary = Some(Err(e)),
+            },
+            Message::DismissImportSummary => self.import_summary = None,
+            Message::GenerateBatchClicked => {
+                self.batch = (0..self.config.batch_size)
+                    .map(|_| Candidate::new(self.roll_unlocked()))
+                    .collect();
+                self.batch_selection.clear();
+            }
+            Message::BatchSizeChanged(index) => {
+                if let Some(&size) = batch::SIZES.get(index) {
+                    if let Err(e) = self.set_batch_size(size) {
+                        eprintln!("Error saving batch size: {:?}", e);
+                    }
+                }
+            }
+            Message::ShowCandidate(index) => {
+                if let Some(candidate) = self.batch.get(index) {
+                    self.show_oc(candidate.oc.clone());
+                }
+            }
+            Message::RerollCandidate(index) => {
+                if index < self.batch.len() {
+                    self.batch[index] = Candidate::new(self.roll_unlocked());
+                    // The ticked OC is gone, don't save its replacement by surprise
+                    self.batch_selection.remove(&index);
+                }
+            }
+            Message::CandidateSelectionToggled(index, selected) => {
+                if selected {
+                    self.batch_selection.insert(index);
+                } else {
+                    self.batch_selection.remove(&index);
+                }
+            }
+            Message::SaveCandidate(index) => self.save_candidates(&[index]),
+            Message::SaveSelectedCandidates => {
+                let mut selected: Vec<usize> = self.batch_selection.drain().collect();
+                selected.sort_unstable();
+                self.save_candidates(&selected);
+            }
+            Message::CloseBatch => {
+                self.batch.clear();
+                self.batch_selection.clear();
+            }
+            Message::GeneratePartyClicked => {
+                self.party = Some(self.roll_party());
+                self.party_name_input.clear();
+            }
+            Message::PartySizeChanged(index) => {
+                if let Some(&size) = party::SIZES.get(index) {
+                    if let Err(e) = self.set_party_size(size) {
+                        eprintln!("Error saving party size: {:?}", e);
+                    }
+                }
+            }
+            Message::ShowPartyMember(index) => {
+                if let Some(member) = self.party.as_ref().and_then(|party| party.members.get(index)) {
+                    self.show_oc(member.oc.clone());
+                }
+            }
+            Message::PartyNameChanged(name) => self.party_name_input = name,
+            Message::SaveParty => self.save_party(),
+            Message::CloseParty => self.party = None,
+            Message::RateOc(liked) => {
+                if let Some(oc) = &self.oc {
+                    let mut weights = self.config.entry_weights.clone();
+                    weights::rate(&mut weights, &oc.traits, liked);
+                    if let Err(e) = self.set_entry_weights(weights) {
+                        eprintln!("Error saving weights: {:?}", e);
+                    }
+                }
+            }
+            Message::LikeBiasChanged(bias) => self.like_bias_dragged = Some(bias),
+            Message::LikeBiasReleased => {
+                if let Some(bias) = self.like_bias_dragged.take() {
+                    if let Err(e) = self.set_like_bias(bias) {
+                        eprintln!("Error saving like bias: {:?}", e);
+                    }
+                }
+            }
+            Message::ShowWeights(show) => self.show_weights = show,
+            Message::ShareFormatChanged(index) => {
+                if let Some(&format) = ShareFormat::ALL.get(index) {
+                    self.share_format = format;
+                }
+            }
+            Message::CopyCurrentOc => {
+                if let Some(oc) = &self.oc {
+                    let character = self.unsaved_oc(oc, 0);
+                    return self.copy_to_clipboard(&character);
+                }
+            }
+            Message::CopyCharacter(id) => {
+                if let Some(character) = self.character(id) {
+                    return self.copy_to_clipboard(&character.clone());
+                }
+            }
+            Message::ResetWeights => {
+                if let Err(e) = self.set_entry_weights(BTreeMap::new()) {
+                    eprintln!("Error saving weights: {:?}", e);
+                }
+            }
+            Message::KeepHistoryToggled(keep) => {
+                if let Err(e) = self.set_keep_history(keep) {
+                    eprintln!("Error saving history setting: {:?}", e);
+                }
+            }
+            Message::OpenCharacter(id) => {
+                self.selected_character = Some(id);
+                self.sheet_draft = None;
+                self.relationship_draft = RelationshipDraft::default();
+                self.refresh_relationship_targets();
+            }
+            Message::ShowRelationshipGraph(show) => self.show_graph = show,
+            Message::RelationshipTargetSelected(index) => {
+                self.relationship_draft.target = Some(index);
+            }
+            Message::RelationshipKindSelected(index) => self.relationship_draft.kind = index,
+            Message::RelationshipCustomChanged(name) => self.relationship_draft.custom = name,
+            Message::AddRelationship => {
+                let draft = &self.relationship_draft;
+                let target = draft
+                    .target
+                    .and_then(|index| self.relationship_targets.get(index));
+                if let (Some(id), Some(&other), Some(kind)) = (self.selected_character, target, draft.kind()) {
+                    let relationship = Relationship::new(id, other, kind);
+                    if !self.relationships.iter().any(|r| r.same_as(&relationship)) {
+                        self.relationships.push(relationship);
+                        if let Err(e) = self.save_characters() {
+                            eprintln!("Error saving relationships: {:?}", e);
+                        }
+                    }
+                    self.relationship_draft = RelationshipDraft::default();
+                }
+            }
+            Message::RemoveRelationship(relationship) => {
+                self.relationships.retain(|r| !r.same_as(&relationship));
+                if let Err(e) = self.save_characters() {
+                    eprintln!("Error saving relationships: {:?}", e);
+                }
+            }
+            Message::CloseCharacter => {
+                self.selected_character = None;
+                self.sheet_draft = None;
+            }
+            Message::EditCharacter => {
+                if let Some(saved) = self
+                    .selected_character
+                    .and_then(|id| self.character(id))
+                    .filter(|saved| !self.is_hidden(saved))
+                {
+                    let tags = saved.tags.join(", ");
+                    self.sheet_draft = Some(
+                        SheetField::ALL
+                            .iter()
+                            .map(|&field| (field, saved.sheet.field(field)))
+                            .collect(),
+                    );
+                    self.tags_draft = tags;
+                }
+            }
+            Message::SheetFieldChanged(field, value) => {
+                if let Some(draft) = &mut self.sheet_draft {
+                    draft.insert(field, value);
+                }
+            }
+            Message::SaveCharacterEdits => {
+                let id = self.selected_character;
+                if let (Some(saved), Some(draft)) = (
+                    id.and_then(|id| self.saved_characters.iter_mut().find(|c| c.id == id)),
+                    self.sheet_draft.take(),
+                ) {
+                    for (field, value) in draft {
+                        saved.sheet.set_field(field, value);
+                    }
+                    saved.tags = split_tags(&self.tags_draft);
+
+                    if let Err(e) = self.save_characters() {
+                        eprintln!("Error saving characters: {:?}", e);
+                    }
+                    // The name may have changed
+                    self.refresh_relationship_targets();
+                }
+            }
+            Message::CancelCharacterEdits => self.sheet_draft = None,
+            Message::ExportPalette(palette, name, format) => {
+                let name = if name.is_empty() { fl!("oc-generator") } else { name };
+                let file_name = format!("{name}.{}", format.extension());
+                let contents = palette.export(format, &name);
+                return save_file(file_name, move || Ok(contents.into_bytes()));
+            }
+            Message::ExportCard(id, format) => {
+                if let Some(saved) = self.character(id) {
+                    let saved = saved.clone();
+                    let file_name = format!("{}.{}", saved.sheet.title(), format.extension());
+                    // Rasterizing happens after the dialog, off the UI thread
+                    return save_file(file_name, move || card::render(&saved, format));
+                }
+            }
+            Message::ExportCommission(id, format) => {
+                if let Some(saved) = self.character(id) {
+                    let saved = saved.clone();
+                    let file_name = strip_isolation_marks(&fl!(
+                        "commission-file-name",
+                        name = saved.sheet.title(),
+                        extension = format.extension()
+                    ));
+                    // Every pack, switched off ones may still know the character's attributes
+                    let pools = WordPools::merge(&self.packs, &[]);
+                    // The template is read after the dialog so edits made meanwhile count
+                    return save_file(file_name, move || {
+                        let template = commission::template(format);
+                        Ok(commission::render(&saved, format, &template, &pools).into_bytes())
+                    });
+                }
+            }
+            Message::EditCommissionTemplates => match commission::write_default_templates() {
+                Ok(dir) => {
+                    if let Err(e) = open::that_detached(&dir) {
+                        eprintln!("Error opening template folder: {:?}", e);
+                    }
+                }
+                Err(e) => eprintln!("Error writing templates: {e}"),
+            },
+            Message::FileSaved(result) => {
+                let message = match result {
+                    Ok(Some(path)) => fl!(
+                        "file-saved",
+                        file = path.file_name().unwrap_or_default().to_string_lossy()
+                    ),
+                    Ok(None) => return cosmic::Task::none(),
+                    Err(e) => {
+                        eprintln!("Error saving file: {e}");
+                        fl!("file-save-failed", error = e)
+                    }
+                };
+                return self
+                    .toasts
+                    .push(widget::toaster::Toast::new(message))
+                    .map(cosmic::Action::App);
+            }
+            Message::MatureContentToggled(mature) => {
+                let rating = if mature {
+                    ContentRating::Mature
+                } else {
+                    ContentRating::Sfw
+                };
+                if let Err(e) = self.set_content_rating(rating) {
+                    eprintln!("Error saving content rating: {:?}", e);
+                }
+                // The OC on screen may use entries that are now filtered, roll a fresh one
+                if self.oc.as_ref().is_some_and(|oc| self.uses_hidden_entries(&oc.traits)) {
+                    if let Some(oc) = &mut self.oc {
+                        oc.locked.clear();
+                    }
+                    self.reroll_unlocked();
+                }
+            }
+            Message::RevealCharacter(id) => {
+                self.revealed_characters.insert(id);
+            }
+            Message::SearchChanged(query) => self.search_query = query,
+            Message::SortChanged(index) => {
+                if let Some(order) = SortOrder::ALL.get(index) {
+                    self.sort_order = *order;
+                    if let Err(e) = self.save_characters() {
+                        eprintln!("Error saving sort order: {:?}", e);
+                    }
+                }
+            }
+            Message::TogglePin(id) => {
+                if let Some(saved) = self.saved_characters.iter_mut().find(|c| c.id == id) {
+                    saved.pinned = !saved.pinned;
+                    if let Err(e) = self.save_characters() {
+                        eprintln!("Error saving characters: {:?}", e);
+                    }
+                }
+            }
+            Message::MoveCharacter(id, up) => self.move_character(id, up),
+            Message::SpeciesFilterToggled(species) => {
+                if self.species_filter.as_ref() == Some(&species) {
+                    self.species_filter = None;
+                } else {
+                    self.species_filter = Some(species);
+                }
+            }
+            Message::TagFilterToggled(tag) => {
+                if self.tag_filter.as_ref() == Some(&tag) {
+                    self.tag_filter = None;
+                } else {
+                    self.tag_filter = Some(tag);
+                }
+            }
+            Message::TagsChanged(tags) => self.tags_draft = tags,
+            Message::CollectionSelected(index) => {
+                // Index 0 is "all characters", the rest line up with `collections`
+                self.selected_collection = index
+                    .checked_sub(1)
+                    .and_then(|i| self.collections.get(i))
+                    .cloned();
+            }
+            Message::NewCollectionInputChanged(input) => self.new_collection_input = input,
+            Message::CreateCollection => {
+                let name = self.new_collection_input.trim().to_string();
+                if !name.is_empty() && !self.collections.contains(&name) {
+                    self.collections.push(name.clone());
+                    self.refresh_collection_labels();
+                    self.selected_collection = Some(name);
+                    self.new_collection_input.clear();
+
+                    if let Err(e) = self.save_characters() {
+                        eprintln!("Error saving collections: {:?}", e);
+                    }
+                }
+            }
+            Message::DeleteCollection => {
+                if let Some(name) = self.selected_collection.take() {
+                    self.collections.retain(|collection| collection != &name);
+                    for character in &mut self.saved_characters {
+                        character.collections.retain(|collection| collection != &name);
+                    }
+                    self.refresh_collection_labels();
+
+                    if let Err(e) = self.save_characters() {
+                        eprintln!("Error saving collections: {:?}", e);
+                    }
+                }
+            }
+            Message::ShowTrash(show) => {
+                self.show_trash = show;
+                self.purge_expired_trash();
+            }
+            Message::RestoreCharacter(id) => {
+                if let Some(position) = self.trash.iter().position(|t| t.character.id == id) {
+                    let trashed = self.trash.remove(position);
+                    self.saved_characters.push(trashed.character);
+                    let saved = &self.saved_characters;
+                    relationships::restore(
+                        id,
+                        trashed.relationships,
+                        &mut self.relationships,
+                        &mut self.trash,
+                        |other| saved.iter().any(|c| c.id == other),
+                    );
+
+                    if let Err(e) = self.save_characters() {
+                        eprintln!("Error saving characters: {:?}", e);
+                    }
+                }
+            }
+            Message::PurgeCharacter(id) => {
+                self.trash.retain(|t| t.character.id != id);
+                if let Err(e) = self.save_characters() {
+                    eprintln!("Error saving characters: {:?}", e);
+                }
+            }
+            Message::EmptyTrash => {
+                self.trash.clear();
+                if let Err(e) = self.save_characters() {
+                    eprintln!("Error saving characters: {:?}", e);
+                }
+            }
+            Message::TrashRetentionChanged(index) => {
+                if let Some(&days) = TRASH_RETENTION_DAYS.get(index) {
+                    if let Err(e) = self.set_trash_retention(days) {
+                        eprintln!("Error saving trash retention: {:?}", e);
+                    }
+                    self.purge_expired_trash();
+                }
+            }
+            Message::CloseToast(id) => self.toasts.remove(id),
+            Message::CollectionMembershipToggled(name, member) => {
+                let id = self.selected_character;
+                if let Some(saved) = id.and_then(|id| self.saved_characters.iter_mut().find(|c| c.id == id)) {
+                    saved.collections.retain(|collection| collection != &name);
+                    if member {
+                        saved.collections.push(name);
+                    }
+
+                    if let Err(e) = self.save_characters() {
+                        eprintln!("Error saving characters: {:?}", e);
+                    }
                 }
             }
         }
@@ -246,6 +2047,10 @@ impl OcGeneratorPage {
 
         let characters_config = SavedCharactersConfig {
             characters: self.saved_characters.clone(),
+            collections: self.collections.clone(),
+            trash: self.trash.clone(),
+            relationships: self.relationships.clone(),
+            sort_order: self.sort_order,
         };
 
         // Store the entire list under one key
@@ -256,103 +2061,997 @@ impl OcGeneratorPage {
     /// Load saved characters from config
     fn load_characters(&mut self) -> Result<(), cosmic::cosmic_config::Error> {
         //Note, the ? operator will return early if there's an error, it's a nice shorthand
+        let (characters_config, mut migrated) = read_saved_characters()?;
+
+        self.saved_characters = characters_config.characters;
+        self.collections = characters_config.collections;
+        self.trash = characters_config.trash;
+        self.relationships = characters_config.relationships;
+        self.sort_order = characters_config.sort_order;
+        self.refresh_collection_labels();
+
+        // Saves from before character sheets only have a sentence, move it into the sheet
+        for character in &mut self.saved_characters {
+            migrated |= character.migrate();
+        }
+
+        // Saves from before ids need one before anything can point at them
+        for index in 0..self.saved_characters.len() {
+            if self.saved_characters[index].id == 0 {
+                self.saved_characters[index].id = self.new_character_id();
+                migrated = true;
+            }
+        }
+        // Edges to characters that are gone, from hand edited configs
+        let ids: HashSet<CharacterId> = self.saved_characters.iter().map(|c| c.id).collect();
+        let relationship_count = self.relationships.len();
+        self.relationships.retain(|r| r.between.iter().all(|id| ids.contains(id)));
+        migrated |= self.relationships.len() != relationship_count;
+
+        if migrated {
+            self.save_characters()?;
+        }
+
+        self.purge_expired_trash();
+        Ok(())
+    }
+
+    /// Everyone the selected character could have a relationship with, minus hidden characters
+    fn refresh_relationship_targets(&mut self) {
+        let selected = self.selected_character;
+        let targets: Vec<&SavedOC> = self
+            .saved_characters
+            .iter()
+            .filter(|c| Some(c.id) != selected && !self.is_hidden(c))
+            .collect();
+
+        self.relationship_target_labels = targets.iter().map(|c| c.sheet.title().to_string()).collect();
+        self.relationship_targets = targets.iter().map(|c| c.id).collect();
+    }
+
+    /// Keep the collection dropdown's labels in step with `collections`
+    fn refresh_collection_labels(&mut self) {
+        self.collection_labels = std::iter::once(fl!("all-characters"))
+            .chain(self.collections.iter().cloned())
+            .collect();
+    }
+
+    /// Move a character to the trash
+    fn delete_character(&mut self, id: CharacterId) -> Result<(), cosmic::cosmic_config::Error> {
+        if let Some(position) = self.saved_characters.iter().position(|c| c.id == id) {
+            let character = self.saved_characters.remove(position);
+            // The trash keeps them for restoring
+            let relationships = relationships::take_involving(&mut self.relationships, id);
+            self.trash.push(TrashedOC {
+                character,
+                deleted_at: chrono::Utc::now().timestamp_millis() as u64,
+                relationships,
+            });
+        }
+        self.save_characters()
+    }
+
+    /// Merge imported characters into the saved ones, skipping ones we already have
+    fn import_characters(&mut self, characters: Vec<SavedOC>) -> ImportSummary {
+        let mut summary = ImportSummary::default();
+
+        for mut character in characters {
+            character.migrate();
+            let title = character.sheet.title().to_string();
+
+            // Characters in the trash count too, restoring one would make a second copy
+            let existing = self
+                .saved_characters
+                .iter()
+                .chain(self.trash.iter().map(|trashed| &trashed.character));
+            if export::is_duplicate(&character, existing) {
+                summary.skipped.push(title);
+                continue;
+            }
+
+            // A different character with the same id, or one from before ids, gets a fresh id
+            if character.id == 0 || self.id_taken(character.id) {
+                character.id = self.new_character_id();
+            }
+            // Collections come along, so make sure they exist here too
+            for collection in &character.collections {
+                if !self.collections.contains(collection) {
+                    self.collections.push(collection.clone());
+                }
+            }
+
+            self.saved_characters.push(character);
+            summary.added.push(title);
+        }
+
+        if !summary.added.is_empty() {
+            self.refresh_collection_labels();
+            if let Err(e) = self.save_characters() {
+                eprintln!("Error saving imported characters: {:?}", e);
+            }
+        }
+        summary
+    }
+
+    /// Drop trashed characters older than the retention period
+    fn purge_expired_trash(&mut self) {
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let retention = u64::from(self.config.trash_retention_days) * DAY_MILLIS;
+
+        let before = self.trash.len();
+        self.trash
+            .retain(|trashed| now.saturating_sub(trashed.deleted_at) < retention);
+
+        if self.trash.len() != before {
+            if let Err(e) = self.save_characters() {
+                eprintln!("Error purging trash: {:?}", e);
+            }
+        }
+    }
+
+    fn character(&self, id: CharacterId) -> Option<&SavedOC> {
+        self.saved_characters.iter().find(|character| character.id == id)
+    }
+
+    /// Ids also stay taken while a character is in the trash, so restoring can't clash
+    fn id_taken(&self, id: CharacterId) -> bool {
+        self.character(id).is_some() || self.trash.iter().any(|t| t.character.id == id)
+    }
+
+    /// A random id that no saved character uses yet, never 0
+    fn new_character_id(&mut self) -> CharacterId {
+        loop {
+            let id = self.rng.u64(1..);
+            if !self.id_taken(id) {
+                return id;
+            }
+        }
+    }
+    /// Reroll every trait that isn't locked, from a fresh seed
+    fn reroll_unlocked(&mut self) {
+        let oc = self.roll_unlocked();
+        self.show_oc(oc);
+    }
+
+    /// A seed for a fresh roll, leaning towards liked entries as far as the bias asks
+    ///
+    /// With the bias at zero this is one plain seed, exactly like before weights existed.
+    fn liked_seed(&mut self) -> u64 {
+        let first = seed::new_seed(&mut self.rng);
+        if self.config.like_bias == 0 || self.config.entry_weights.is_empty() {
+            return first;
+        }
+
+        let mut seeds = vec![first];
+        seeds.extend((1..weights::CANDIDATES).map(|_| seed::new_seed(&mut self.rng)));
+        let scores: Vec<f64> = seeds
+            .iter()
+            .map(|&candidate| {
+                weights::score(
+                    &self.config.entry_weights,
+                    &self.generate(candidate),
+                    self.config.like_bias,
+                )
+            })
+            .collect();
+        seeds[weights::pick(&mut self.rng, &scores)]
+    }
+
+    /// Roll a new OC, keeping the current OC's locked traits
+    fn roll_unlocked(&mut self) -> GeneratedOC {
+        let new_seed = self.liked_seed();
+        // Made-up names come with the species of their own seed, not a kept one, so the seed
+        // code rebuilds the same name
+        let mut traits = self.generate(new_seed);
+        let mut locked = vec![];
+
+        if let Some(current) = &self.oc {
+            for oc_trait in &mut traits {
+                if let Some(kept) = current.get(oc_trait.slot).filter(|_| current.is_locked(oc_trait.slot)) {
+                    *oc_trait = kept.clone();
+                }
+            }
+            locked = current.locked.clone();
+        }
+
+        let palette = self.palette_for(&traits);
+        GeneratedOC {
+            traits,
+            locked,
+            palette,
+        }
+    }
+
+    /// Swap a character with its neighbor in the list as shown, if that one is pinned the same way
+    ///
+    /// Only the two characters trade places in `saved_characters`, so characters filtered out
+    /// right now keep their place relative to everyone else.
+    fn move_character(&mut self, id: CharacterId, up: bool) {
+        let visible: Vec<(CharacterId, bool)> = self
+            .visible_characters()
+            .iter()
+            .map(|c| (c.id, c.pinned))
+            .collect();
+        let Some(index) = visible.iter().position(|&(visible_id, _)| visible_id == id) else {
+            return;
+        };
+        let neighbor = if up {
+            index.checked_sub(1).and_then(|i| visible.get(i))
+        } else {
+            visible.get(index + 1)
+        };
+        let Some(&(neighbor, _)) = neighbor.filter(|&&(_, pinned)| pinned == visible[index].1) else {
+            return;
+        };
+
+        let position = |id| self.saved_characters.iter().position(|c| c.id == id);
+        if let (Some(a), Some(b)) = (position(id), position(neighbor)) {
+            self.saved_characters.swap(a, b);
+            if let Err(e) = self.save_characters() {
+                eprintln!("Error saving character order: {:?}", e);
+            }
+        }
+    }
+
+    /// Put a character on the clipboard in the picked format and say so in a toast
+    fn copy_to_clipboard(&mut self, character: &SavedOC) -> cosmic::Task<cosmic::Action<Message>> {
+        let format = self.share_format;
+        match share::share(character, format) {
+            Ok(text) => {
+                let toast = self
+                    .toasts
+                    .push(widget::toaster::Toast::new(fl!("copied", format = format.label())))
+                    .map(cosmic::Action::App);
+                cosmic::Task::batch([cosmic::iced::clipboard::write(text), toast])
+            }
+            Err(e) => {
+                eprintln!("Error copying character: {:?}", e);
+                self.toasts
+                    .push(widget::toaster::Toast::new(fl!("copy-failed")))
+                    .map(cosmic::Action::App)
+            }
+        }
+    }
+
+    /// Roll a whole party from fresh seeds, ignoring locks, with no species twice if the pools allow
+    fn roll_party(&mut self) -> Party {
+        let mut rng = fastrand::Rng::with_seed(seed::new_seed(&mut self.rng));
+        let roles = party::roles(&mut rng, self.config.party_size as usize);
+
+        let species_id = |traits: &[OcTrait]| {
+            traits
+                .iter()
+                .find(|t| t.slot == TraitSlot::Species)
+                .map(|t| t.entry_id.clone())
+                .unwrap_or_default()
+        };
+        let mut taken = HashSet::new();
+        let mut members = vec![];
+        for &role in &roles {
+            let mut traits = vec![];
+            for _ in 0..party::SPECIES_ATTEMPTS {
+                let member_seed = self.liked_seed();
+                traits = self.generate(member_seed);
+                if !taken.contains(&species_id(&traits)) {
+                    break;
+                }
+            }
+            taken.insert(species_id(&traits));
+
+            members.push(Member {
+                oc: GeneratedOC {
+                    palette: self.palette_for(&traits),
+                    traits,
+                    locked: vec![],
+                },
+                role,
+            });
+        }
+
+        Party {
+            members,
+            bonds: party::bonds(&mut rng, &roles),
+            saved: false,
+        }
+    }
+
+    /// Save the party's members and relationships, all in a new collection
+    fn save_party(&mut self) {
+        let Some(party) = self.party.clone().filter(|party| !party.saved) else {
+            return;
+        };
+
+        let leader = party
+            .members
+            .first()
+            .and_then(|member| member.oc.get(TraitSlot::Name))
+            .map(|t| t.text.clone())
+            .unwrap_or_default();
+        let typed = self.party_name_input.trim();
+        let base = if typed.is_empty() {
+            strip_isolation_marks(&fl!("party-default-name", leader = leader))
+        } else {
+            typed.to_string()
+        };
+        // Don't quietly merge into an existing collection
+        let mut name = base.clone();
+        let mut number = 2;
+        while self.collections.contains(&name) {
+            name = format!("{base} ({number})");
+            number += 1;
+        }
+
+        let mut ids = vec![];
+        for member in &party.members {
+            let mut saved = self.saved_oc(&member.oc);
+            saved.role = Some(member.role);
+            saved.collections.push(name.clone());
+            ids.push(saved.id);
+            self.saved_characters.push(saved);
+        }
+        for bond in party.bonds {
+            let [first, second] = bond.between.map(|index| ids[index]);
+            self.relationships.push(Relationship::new(first, second, bond.kind));
+        }
+
+        self.collections.push(name.clone());
+        self.refresh_collection_labels();
+        self.selected_collection = Some(name);
+        self.party_name_input.clear();
+        if let Some(party) = &mut self.party {
+            party.saved = true;
+        }
+
+        if let Err(e) = self.save_characters() {
+            eprintln!("Error saving party: {:?}", e);
+        }
+    }
+
+    /// A generated OC as a new favorite, ready to push onto `saved_characters`
+    fn saved_oc(&mut self, oc: &GeneratedOC) -> SavedOC {
+        let id = self.new_character_id();
+        self.unsaved_oc(oc, id)
+    }
+
+    /// A generated OC in the saved format without giving it an id of its own, for sharing
+    fn unsaved_oc(&self, oc: &GeneratedOC, id: CharacterId) -> SavedOC {
+        let mut saved = SavedOC::new(id, oc.to_sheet(self.pools.grammar()));
+        saved.seed = oc.shared_seed();
+        saved.traits = oc.traits.clone();
+        saved.pack_versions = self.pack_versions();
+        saved.roll_settings = Some(RollSettings::of(&self.config));
+        saved.palette = oc.palette.clone();
+        saved
+    }
+
+    /// Save batch candidates to the favorites in one write, skipping ones already saved
+    fn save_candidates(&mut self, indices: &[usize]) {
+        for &index in indices {
+            let Some(candidate) = self.batch.get(index).filter(|c| !c.saved) else {
+                continue;
+            };
+            let oc = candidate.oc.clone();
+            let new_saved = self.saved_oc(&oc);
+            self.saved_characters.push(new_saved);
+            self.batch[index].saved = true;
+        }
+
+        if let Err(e) = self.save_characters() {
+            eprintln!("Error saving characters: {:?}", e);
+        }
+    }
+
+    /// Show a new OC and add it to the history
+    fn show_oc(&mut self, oc: GeneratedOC) {
+        self.history.push(oc.clone());
+        self.oc = Some(oc);
+
+        if self.config.keep_history {
+            if let Err(e) = self.save_history() {
+                eprintln!("Error saving history: {:?}", e);
+            }
+        }
+    }
+
+    fn save_history(&self) -> Result<(), cosmic::cosmic_config::Error> {
         let config = Config::new(CONFIG_KEY, SavedCharactersConfig::VERSION)?;
+        config.set("history", self.history.to_vec())
+    }
+
+    /// Pick up the history from the last session, if there is one
+    fn load_history(&mut self) {
+        let history = Config::new(CONFIG_KEY, SavedCharactersConfig::VERSION)
+            .and_then(|config| config.get::<Vec<GeneratedOC>>("history"));
+
+        if let Ok(entries) = history {
+            self.history = History::from_entries(entries);
+            self.oc = self.history.current().cloned();
+        }
+    }
+
+    /// Switch history persistence on or off, forgetting the stored history when it's off
+    fn set_keep_history(&mut self, keep: bool) -> Result<(), cosmic::cosmic_config::Error> {
+        let config = Config::new(CONFIG_KEY, AppConfig::VERSION)?;
+        self.config.set_keep_history(&config, keep)?;
+
+        if keep {
+            self.save_history()
+        } else {
+            let config = Config::new(CONFIG_KEY, SavedCharactersConfig::VERSION)?;
+            config.set("history", Vec::<GeneratedOC>::new())
+        }
+    }
+
+    /// Rebuild an OC from one shared seed, or one seed per slot
+    ///
+    /// A made-up name already fits the species of its own seed. That is this OC's species when
+    /// the two slots share a seed. When they don't, one of them was kept through a reroll, and
+    /// making the name up again for the other species would change it.
+    fn generate_from_seeds(&self, seeds: &[u64]) -> GeneratedOC {
+        let traits = TraitSlot::ALL
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &slot)| {
+                let slot_seed = seeds.get(index).or(seeds.first())?;
+                self.generate(*slot_seed).into_iter().find(|t| t.slot == slot)
+            })
+            .collect::<Vec<_>>();
+
+        GeneratedOC {
+            palette: self.palette_for(&traits),
+            traits,
+            locked: vec![],
+        }
+    }
+
+    /// Roll colors from the species trait, inside the range its pack entry gives
+    ///
+    /// Hybrids use the range of their first parent.
+    fn palette_for(&self, traits: &[OcTrait]) -> Option<Palette> {
+        let species = traits.iter().find(|t| t.slot == TraitSlot::Species)?;
+        let entry_id = species
+            .parents
+            .first()
+            .map_or(&species.entry_id, |parent| &parent.entry_id);
+        let range = self
+            .pools
+            .entries(packs::SPECIES)
+            .iter()
+            .find(|entry| &entry.id == entry_id)
+            .and_then(|entry| entry.palette)
+            .unwrap_or_default();
+
+        Some(Palette::generate(&range, species.seed))
+    }
+
+    /// Roll every trait from one seed
+    ///
+    /// All randomness comes from the seed and slots are always rolled in the same order, so the
+    /// same seed and packs always give the same OC
+    fn generate(&self, oc_seed: u64) -> Vec<OcTrait> {
+        let mut rng = fastrand::Rng::with_seed(oc_seed);
+
+        let mut traits = TraitSlot::ALL
+            .iter()
+            .map(|&slot| {
+                let entry = match slot {
+                    TraitSlot::Attribute => self.generate_oc_attribute(&mut rng),
+                    TraitSlot::Species => self.generate_oc_species(&mut rng),
+                    TraitSlot::Characteristic => self.generate_characteristic(&mut rng),
+                    slot => self.pick_from_pool(&mut rng, slot.category()),
+                };
+
+                let mut oc_trait = OcTrait {
+                    slot,
+                    entry_id: entry.map(|e| e.id.clone()).unwrap_or_default(),
+                    text: entry.map(PackEntry::label).unwrap_or_default(),
+                    seed: oc_seed,
+                    parents: vec![],
+                    features: entry.map(PackEntry::features).unwrap_or_default(),
+                };
+                if let (TraitSlot::Species, Some(entry)) = (slot, entry) {
+                    self.hybridize(&mut oc_trait, entry);
+                }
+                oc_trait
+            })
+            .collect::<Vec<_>>();
+        self.make_up_name(&mut traits);
+        traits
+    }
+
+    /// Swap the name from the packs for a made-up one that fits the species, if switched on
+    ///
+    /// The pack name still gets picked first so the other traits a seed gives stay the same,
+    /// and the made-up name rolls with its own rng from the name's seed. Made-up names have no
+    /// pack entry, so their `entry_id` stays empty. Whether names are made up is kept in
+    /// `RollSettings`, a seed gives a different name either way.
+    fn make_up_name(&self, traits: &mut [OcTrait]) {
+        if !self.config.generated_names {
+            return;
+        }
+
+        let flavor = traits
+            .iter()
+            .find(|t| t.slot == TraitSlot::Species)
+            .map_or(names::DEFAULT_FLAVOR, |species| self.name_flavor(species));
+        if let Some(name) = traits.iter_mut().find(|t| t.slot == TraitSlot::Name) {
+            let mut rng = fastrand::Rng::with_seed(name.seed ^ names::NAME_SALT);
+            name.text = names::generate(flavor, &mut rng);
+            name.entry_id = String::new();
+            name.features = BTreeMap::new();
+        }
+    }
+
+    /// The name flavor of a species trait, hybrids take their first parent's
+    fn name_flavor(&self, species: &OcTrait) -> &str {
+        let entry_id = species
+            .parents
+            .first()
+            .map_or(&species.entry_id, |parent| &parent.entry_id);
+        self.pools
+            .entries(packs::SPECIES)
+            .iter()
+            .find(|entry| &entry.id == entry_id)
+            .and_then(|entry| entry.name_flavor.as_deref())
+            .unwrap_or(names::DEFAULT_FLAVOR)
+    }
+
+    /// Maybe turn a rolled species into a hybrid with a second one
+    ///
+    /// This rolls with its own rng so switching hybrids on doesn't change any other trait a
+    /// seed gives, and with hybrids off nothing changes at all. The species itself does depend
+    /// on the hybrid settings, which is why they're kept in `RollSettings`.
+    fn hybridize(&self, species: &mut OcTrait, first: &PackEntry) {
+        let mut rng = fastrand::Rng::with_seed(species.seed ^ hybrid::HYBRID_SALT);
+        if rng.u32(0..100) >= self.config.hybrid_chance {
+            return;
+        }
+
+        let others: Vec<&PackEntry> = self
+            .pools
+            .entries(packs::SPECIES)
+            .iter()
+            .filter(|entry| entry.id != first.id && entry.rating <= self.config.content_rating)
+            .collect();
+        if others.is_empty() {
+            return;
+        }
+        let second = others[rng.usize(..others.len())];
+
+        let named_hybrids: Vec<&PackEntry> = self
+            .pools
+            .entries(packs::HYBRID)
+            .iter()
+            .filter(|entry| entry.rating <= self.config.content_rating)
+            .collect();
 
-        match config.get::<SavedCharactersConfig>("characters") {
-            Ok(characters_config) => {
-                self.saved_characters = characters_config.characters;
-                Ok(())
+        let (text, features) = hybrid::name(self.config.hybrid_naming, &named_hybrids, first, second);
+        species.entry_id = hybrid::pair_id(first, second);
+        species.text = text;
+        species.features = features;
+        species.parents = [first, second]
+            .into_iter()
+            .map(|entry| ParentSpecies {
+                entry_id: entry.id.clone(),
+                text: entry.label(),
+            })
+            .collect();
+    }
+
+    fn generate_oc_attribute(&self, rng: &mut fastrand::Rng) -> Option<&PackEntry> {
+        self.pick_from_pool(rng, packs::ATTRIBUTE)
+    }
+
+    fn generate_oc_species(&self, rng: &mut fastrand::Rng) -> Option<&PackEntry> {
+        self.pick_from_pool(rng, packs::SPECIES)
+    }
+
+    fn generate_characteristic(&self, rng: &mut fastrand::Rng) -> Option<&PackEntry> {
+        self.pick_from_pool(rng, packs::CHARACTERISTIC)
+    }
+
+    /// Pick a random entry from a category, `None` if every pack providing it is switched off
+    ///
+    /// Entries above the content rating in the config are skipped, so the rating is part of what
+    /// a seed rolls, see `RollSettings`
+    fn pick_from_pool(&self, rng: &mut fastrand::Rng, category: &str) -> Option<&PackEntry> {
+        let entries: Vec<&PackEntry> = self
+            .pools
+            .entries(category)
+            .iter()
+            .filter(|entry| entry.rating <= self.config.content_rating)
+            .collect();
+        if entries.is_empty() {
+            return None;
+        }
+
+        Some(entries[rng.usize(0..entries.len())])
+    }
+
+    /// Whether a saved character uses entries above the content rating and hasn't been revealed
+    ///
+    /// Characters without traits, saved before traits or typed in by hand, are checked by
+    /// looking for those entries' text on their sheet instead.
+    fn is_hidden(&self, saved: &SavedOC) -> bool {
+        if self.revealed_characters.contains(&saved.id) {
+            return false;
+        }
+
+        if saved.traits.is_empty() {
+            self.sheet_mentions_hidden_entries(&saved.sheet)
+        } else {
+            self.uses_hidden_entries(&saved.traits)
+        }
+    }
+
+    /// Whether any of the traits, or a hybrid's parents, come from entries above the content rating
+    fn uses_hidden_entries(&self, traits: &[OcTrait]) -> bool {
+        traits.iter().any(|oc_trait| {
+            std::iter::once(&oc_trait.entry_id)
+                .chain(oc_trait.parents.iter().map(|parent| &parent.entry_id))
+                .any(|entry_id| {
+                self.pools
+                    .entries(oc_trait.slot.category())
+                    .iter()
+                    .any(|entry| &entry.id == entry_id && entry.rating > self.config.content_rating)
+            })
+        })
+    }
+
+    /// Whether the text of an entry above the content rating shows up anywhere on a sheet
+    fn sheet_mentions_hidden_entries(&self, sheet: &CharacterSheet) -> bool {
+        let text = SheetField::ALL
+            .into_iter()
+            .map(|field| sheet.field(field).to_lowercase())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        TraitSlot::ALL
+            .iter()
+            .map(|slot| slot.category())
+            .chain([packs::HYBRID])
+            .flat_map(|category| self.pools.entries(category))
+            .filter(|entry| entry.rating > self.config.content_rating)
+            .any(|entry| contains_phrase(&text, &entry.label().to_lowercase()))
+    }
+
+    /// Versions of the enabled packs, which together with a seed pin down an OC
+    fn pack_versions(&self) -> BTreeMap<String, String> {
+        self.packs
+            .iter()
+            .filter(|pack| !self.config.disabled_packs.contains(&pack.id))
+            .map(|pack| (pack.id.clone(), pack.version.clone()))
+            .collect()
+    }
+
+    /// Load the bundled and user packs, keeping any errors around to show in the view
+    fn load_packs(&mut self) {
+        let (packs, errors) = packs::load_packs();
+        for error in &errors {
+            eprintln!("Error loading generator pack: {error}");
+        }
+
+        self.packs = packs;
+        self.pack_errors = errors;
+        self.rebuild_pools();
+    }
+
+    /// Re-merge the word pools after the pack selection changes
+    fn rebuild_pools(&mut self) {
+        self.pools = WordPools::merge(&self.packs, &self.config.disabled_packs);
+        // Packs pick silhouettes and accessories, so every avatar may look different now
+        self.avatars.clear();
+    }
+
+    /// Draw avatars for characters that are new or whose traits or palette changed
+    fn refresh_avatars(&mut self) {
+        let ids: HashSet<CharacterId> = self.saved_characters.iter().map(|c| c.id).collect();
+        self.avatars.retain(|id, _| ids.contains(id));
+
+        for saved in &self.saved_characters {
+            let current = self
+                .avatars
+                .get(&saved.id)
+                .is_some_and(|cached| cached.traits == saved.traits && cached.palette == saved.palette);
+            if !current {
+                let svg = avatar::svg(&saved.traits, saved.palette.as_ref(), &self.pools);
+                self.avatars.insert(
+                    saved.id,
+                    CachedAvatar {
+                        traits: saved.traits.clone(),
+                        palette: saved.palette.clone(),
+                        handle: widget::svg::Handle::from_memory(svg),
+                    },
+                );
             }
-            Err(e) => Err(e),
         }
     }
 
-    /// Delete a character at a given index
-    fn delete_character_at_index(
+    /// A saved character's avatar, drawn on the spot if `refresh_avatars` hasn't got to it yet
+    fn avatar(&self, saved: &SavedOC) -> widget::svg::Handle {
+        match self.avatars.get(&saved.id) {
+            Some(cached) => cached.handle.clone(),
+            None => widget::svg::Handle::from_memory(avatar::svg(
+                &saved.traits,
+                saved.palette.as_ref(),
+                &self.pools,
+            )),
+        }
+    }
+
+    /// Remember the hybrid chance in the app config
+    fn set_hybrid_chance(&mut self, chance: u32) -> Result<(), cosmic::cosmic_config::Error> {
+        let config = Config::new(CONFIG_KEY, AppConfig::VERSION)?;
+        self.config.set_hybrid_chance(&config, chance)?;
+        Ok(())
+    }
+
+    fn set_generated_names(&mut self, generated: bool) -> Result<(), cosmic::cosmic_config::Error> {
+        let config = Config::new(CONFIG_KEY, AppConfig::VERSION)?;
+        self.config.set_generated_names(&config, generated)?;
+        Ok(())
+    }
+
+    fn set_hybrid_naming(&mut self, naming: HybridNaming) -> Result<(), cosmic::cosmic_config::Error> {
+        let config = Config::new(CONFIG_KEY, AppConfig::VERSION)?;
+        self.config.set_hybrid_naming(&config, naming)?;
+        Ok(())
+    }
+
+    fn set_batch_size(&mut self, size: u32) -> Result<(), cosmic::cosmic_config::Error> {
+        let config = Config::new(CONFIG_KEY, AppConfig::VERSION)?;
+        self.config.set_batch_size(&config, size)?;
+        Ok(())
+    }
+
+    fn set_like_bias(&mut self, bias: u32) -> Result<(), cosmic::cosmic_config::Error> {
+        let config = Config::new(CONFIG_KEY, AppConfig::VERSION)?;
+        self.config.set_like_bias(&config, bias)?;
+        Ok(())
+    }
+
+    fn set_entry_weights(&mut self, weights: BTreeMap<String, u32>) -> Result<(), cosmic::cosmic_config::Error> {
+        let config = Config::new(CONFIG_KEY, AppConfig::VERSION)?;
+        self.config.set_entry_weights(&config, weights)?;
+        Ok(())
+    }
+
+    fn set_party_size(&mut self, size: u32) -> Result<(), cosmic::cosmic_config::Error> {
+        let config = Config::new(CONFIG_KEY, AppConfig::VERSION)?;
+        self.config.set_party_size(&config, size)?;
+        Ok(())
+    }
+
+    /// Remember how long deleted characters are kept in the app config
+    fn set_trash_retention(&mut self, days: u32) -> Result<(), cosmic::cosmic_config::Error> {
+        let config = Config::new(CONFIG_KEY, AppConfig::VERSION)?;
+        self.config.set_trash_retention_days(&config, days)?;
+        Ok(())
+    }
+
+    /// Remember the content rating in the app config
+    fn set_content_rating(&mut self, rating: ContentRating) -> Result<(), cosmic::cosmic_config::Error> {
+        let config = Config::new(CONFIG_KEY, AppConfig::VERSION)?;
+        self.config.set_content_rating(&config, rating)?;
+        Ok(())
+    }
+
+    /// Switch a pack on or off and remember the choice in the app config
+    fn set_pack_enabled(
         &mut self,
-        index: usize,
+        pack_id: String,
+        enabled: bool,
     ) -> Result<(), cosmic::cosmic_config::Error> {
-        self.saved_characters.remove(index);
-        self.save_characters()
+        let mut disabled_packs = self.config.disabled_packs.clone();
+        disabled_packs.retain(|id| id != &pack_id);
+        if !enabled {
+            disabled_packs.push(pack_id);
+        }
+
+        let config = Config::new(CONFIG_KEY, AppConfig::VERSION)?;
+        self.config.set_disabled_packs(&config, disabled_packs)?;
+        self.rebuild_pools();
+        Ok(())
+    }
+}
+
+/// Whether `phrase` shows up in `text` as whole words, so "vore" doesn't match "carnivore"
+fn contains_phrase(text: &str, phrase: &str) -> bool {
+    if phrase.is_empty() {
+        return false;
+    }
+    text.match_indices(phrase).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + phrase.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+/// The saved characters as stored, and whether they came from before collections
+fn read_saved_characters() -> Result<(SavedCharactersConfig, bool), cosmic::cosmic_config::Error> {
+    //Note, the ? operator will return early if there's an error, it's a nice shorthand
+    let config = Config::new(CONFIG_KEY, SavedCharactersConfig::VERSION)?;
+
+    match config.get::<SavedCharactersConfig>("characters") {
+        Ok(characters_config) => Ok((characters_config, false)),
+        // Nothing saved under this version yet, bring over the characters from before collections
+        Err(cosmic::cosmic_config::Error::NotFound) => {
+            let legacy = Config::new(CONFIG_KEY, SavedCharactersConfig::LEGACY_VERSION)?;
+            legacy
+                .get::<SavedCharactersConfig>("characters")
+                .map(|characters_config| (characters_config, true))
+        }
+        // Saves that are there but can't be read must not be replaced by the old copy, that
+        // would lose everything saved since the migration
+        Err(e) => Err(e),
     }
-    /// Generate a string from random string elements
-    fn generate(&self) -> String {
-        "A ".to_string()
-            + self.generate_oc_attribute().as_str()
-            + " "
-            + self.generate_oc_species().as_str()
-            + " "
-            + self.generate_characteristic().as_str()
-    }
-
-    fn generate_oc_attribute(&self) -> String {
-        let attributes = [
-            fl!("attribute-short"),
-            fl!("attribute-tall"),
-            fl!("attribute-fat"),
-            fl!("attribute-nervous"),
-            fl!("attribute-brave"),
-            fl!("attribute-shy"),
-            fl!("attribute-curious"),
-            fl!("attribute-friendly"),
-            fl!("attribute-aloof"),
-            fl!("attribute-clever"),
-            fl!("attribute-clumsy"),
-            fl!("attribute-energetic"),
-            fl!("attribute-sleepy"),
-            fl!("attribute-grumpy"),
-            fl!("attribute-optimistic"),
-            fl!("attribute-pessimistic"),
-            fl!("attribute-cunning"),
-            fl!("attribute-kind"),
-            fl!("attribute-sarcastic"),
-            fl!("attribute-micro"),
-            fl!("attribute-macro"),
-        ];
-
-        attributes[fastrand::usize(0..attributes.len())].to_string()
-    }
-
-    fn generate_oc_species(&self) -> String {
-        // unfortuantely must pass fl! a string literal so can't just call it on the computed value
-        let species = [
-            fl!("species-cat"),
-            fl!("species-dog"),
-            fl!("species-fox"),
-            fl!("species-wolf"),
-            fl!("species-rabbit"),
-            fl!("species-horse"),
-            fl!("species-dragon"),
-            fl!("species-lion"),
-            fl!("species-tiger"),
-            fl!("species-deer"),
-            fl!("species-bat"),
-            fl!("species-snake"),
-        ];
-
-        species[fastrand::usize(0..species.len())].to_string()
-    }
-
-    fn generate_characteristic(&self) -> String {
-        let characteristics = [
-            fl!("characteristic-mokawk"),
-            fl!("characteristic-no-pants"),
-            fl!("characteristic-constant-waffles"),
-            fl!("characteristic-earrings"),
-            fl!("characteristic-always-cape"),
-            fl!("characteristic-tiny-squeak"),
-            fl!("characteristic-overdramatic"),
-            fl!("characteristic-secret-nerd"),
-            fl!("characteristic-philosopher"),
-            fl!("characteristic-sings-everything"),
-            fl!("characteristic-hat-collection"),
-            fl!("characteristic-uses-emoji"),
-            fl!("characteristic-collects-bad-jokes"),
-            fl!("characteristic-vore"),
-            fl!("characteristic-ponytail"),
-            fl!("characteristic-sparkle"),
-        ];
-
-        characteristics[fastrand::usize(0..characteristics.len())].to_string()
+}
+
+/// The saved characters for a quick look from outside the app, like the command line
+///
+/// Unlike loading the page this never writes anything back, so it doesn't purge the trash or
+/// save migrations with settings that may not be the user's. Old saves are migrated in memory.
+pub fn load_saved_characters() -> Result<Vec<SavedOC>, cosmic::cosmic_config::Error> {
+    let (characters_config, _) = read_saved_characters()?;
+    let mut characters = characters_config.characters;
+    for character in &mut characters {
+        character.migrate();
+    }
+    Ok(characters)
+}
+
+/// A palette as a strip of color swatches
+fn swatches<'a>(palette: &Palette) -> Element<'a, Message> {
+    let size = palette.swatch_size();
+    widget::svg(widget::svg::Handle::from_memory(palette.swatch_svg()))
+        .width(Length::Fixed(size.0))
+        .height(Length::Fixed(size.1))
+        .into()
+}
+
+/// Ask where to save a file with the portal's save dialog, then write it there
+///
+/// The contents are only built once a path was picked, on a blocking thread since some of
+/// them (PNG cards) take a moment to render. Suggested names are usually character names, so
+/// path separators in them (like in "fox/wolf hybrid") are swapped for dashes.
+fn save_file(
+    file_name: String,
+    contents: impl FnOnce() -> Result<Vec<u8>, String> + Send + 'static,
+) -> cosmic::Task<cosmic::Action<Message>> {
+    let file_name = file_name.replace(['/', '\\'], "-");
+    cosmic::task::future(async move {
+        let dialog = cosmic::dialog::file_chooser::save::Dialog::new()
+            .title(fl!("save-file-title"))
+            .file_name(file_name);
+
+        // The dialog errors when it's cancelled, which isn't worth reporting
+        let Some(path) = dialog
+            .save_file()
+            .await
+            .ok()
+            .and_then(|response| response.url().and_then(|url| url.to_file_path().ok()))
+        else {
+            return Message::FileSaved(Ok(None));
+        };
+
+        let contents = match tokio::task::spawn_blocking(contents).await {
+            Ok(Ok(contents)) => contents,
+            Ok(Err(e)) => return Message::FileSaved(Err(e)),
+            Err(e) => return Message::FileSaved(Err(e.to_string())),
+        };
+
+        let result = tokio::fs::write(&path, contents)
+            .await
+            .map(|()| Some(path))
+            .map_err(|e| e.to_string());
+        Message::FileSaved(result)
+    })
+}
+
+/// Split comma separated tags, dropping blanks and repeats
+fn split_tags(tags: &str) -> Vec<String> {
+    let mut split: Vec<String> = vec![];
+    for tag in tags.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+        if !split.iter().any(|existing| existing == tag) {
+            split.push(tag.to_string());
+        }
+    }
+    split
+}
+
+/// Ask for a file with the portal's open dialog and read it
+fn open_file() -> cosmic::Task<cosmic::Action<Message>> {
+    cosmic::task::future(async move {
+        let dialog = cosmic::dialog::file_chooser::open::Dialog::new().title(fl!("open-file-title"));
+
+        // Cancelling shows up as an error too, same as when saving
+        let Some(path) = dialog
+            .open_file()
+            .await
+            .ok()
+            .and_then(|response| response.url().to_file_path().ok())
+        else {
+            return Message::ImportLoaded(Ok(None));
+        };
+
+        let result = tokio::fs::read_to_string(&path)
+            .await
+            .map(Some)
+            .map_err(|e| e.to_string());
+        Message::ImportLoaded(result)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page() -> OcGeneratorPage {
+        let mut page = OcGeneratorPage::default();
+        page.load_packs();
+        page.config.generated_names = true;
+        page
+    }
+
+    /// Reroll with one slot locked and check every result rebuilds from its seed code
+    fn assert_rerolls_rebuild(locked: TraitSlot) {
+        let mut page = page();
+        let mut oc = page.roll_unlocked();
+        oc.locked = vec![locked];
+        page.oc = Some(oc);
+
+        for _ in 0..20 {
+            let oc = page.roll_unlocked();
+            let seeds = seed::decode_all(&oc.seed_code()).unwrap();
+            assert_eq!(page.generate_from_seeds(&seeds).traits, oc.traits);
+            page.oc = Some(oc);
+        }
+    }
+
+    #[test]
+    fn avatars_follow_their_characters() {
+        let mut page = page();
+        let oc = page.roll_unlocked();
+        let saved = page.saved_oc(&oc);
+        page.saved_characters.push(saved);
+        let id = page.saved_characters[0].id;
+        page.refresh_avatars();
+        assert_eq!(page.avatars[&id].palette, oc.palette);
+
+        page.saved_characters[0].palette = None;
+        page.refresh_avatars();
+        assert_eq!(page.avatars[&id].palette, None);
+
+        page.saved_characters.clear();
+        page.refresh_avatars();
+        assert!(page.avatars.is_empty());
+    }
+
+    #[test]
+    fn locked_name_rerolls_rebuild() {
+        assert_rerolls_rebuild(TraitSlot::Name);
+    }
+
+    #[test]
+    fn locked_species_rerolls_rebuild() {
+        assert_rerolls_rebuild(TraitSlot::Species);
+    }
+
+    #[test]
+    fn party_members_have_different_species() {
+        let mut page = page();
+        page.config.party_size = *party::SIZES.last().unwrap();
+        for _ in 0..10 {
+            let party = page.roll_party();
+            assert_eq!(party.members.len(), page.config.party_size as usize);
+
+            let species: HashSet<&str> = party
+                .members
+                .iter()
+                .filter_map(|member| member.oc.get(TraitSlot::Species))
+                .map(|species| species.entry_id.as_str())
+                .collect();
+            assert_eq!(species.len(), party.members.len());
+        }
     }
 }
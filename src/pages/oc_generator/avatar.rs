@@ -0,0 +1,452 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Little avatars for saved characters
+//!
+//! Not a portrait, just enough to tell characters apart at a glance in a long list. The head
+//! and ears come from the species (a hybrid gets the head of one parent and the ears of the
+//! other), the colors from the palette and an accessory from the characteristic. Packs pick
+//! them with `silhouette` on species and `accessory` on characteristics, entries without one
+//! fall back to a table for the default pack.
+//!
+//! Everything is picked from the stored traits and palette, so a character always gets the
+//! same avatar, on every machine and after every restart. Small details like markings are
+//! varied with an rng seeded from the traits' own seeds.
+
+use super::character::{OcTrait, TraitSlot};
+use super::packs::{self, WordPools};
+use super::palette::{Palette, SwatchRole};
+
+/// Width and height of the avatar, in the same units as the drawing
+pub const SIZE: f32 = 64.0;
+
+/// Colors for characters without a palette, like ones saved before palettes existed
+const FALLBACK_MAIN: &str = "#9a8f85";
+const FALLBACK_ACCENT: &str = "#5c5470";
+const FALLBACK_MARKING: &str = "#e8e0d8";
+const EYES: &str = "#1e1e2a";
+const GOLD: &str = "#f2c14e";
+
+/// Head and ear shapes, shared by species that look alike
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Silhouette {
+    Feline,
+    Canine,
+    Rabbit,
+    Horse,
+    Deer,
+    Dragon,
+    Snake,
+    Bat,
+    /// Species the avatars don't know, from other packs or typed in by hand
+    Round,
+}
+
+impl Silhouette {
+    /// The silhouette a species entry asks for, or the default pack's for its id
+    fn of(entry_id: &str, pools: &WordPools) -> Self {
+        let named = pools
+            .entries(packs::SPECIES)
+            .iter()
+            .find(|entry| entry.id == entry_id)
+            .and_then(|entry| entry.silhouette.as_deref());
+        named.map_or_else(|| Self::default_for(entry_id), Self::named)
+    }
+
+    /// By the name packs use for it, `round` for names the avatars don't know
+    fn named(name: &str) -> Self {
+        match name {
+            "feline" => Silhouette::Feline,
+            "canine" => Silhouette::Canine,
+            "rabbit" => Silhouette::Rabbit,
+            "horse" => Silhouette::Horse,
+            "deer" => Silhouette::Deer,
+            "dragon" => Silhouette::Dragon,
+            "snake" => Silhouette::Snake,
+            "bat" => Silhouette::Bat,
+            _ => Silhouette::Round,
+        }
+    }
+
+    /// For species of the default pack, which doesn't spell its silhouettes out
+    fn default_for(entry_id: &str) -> Self {
+        match entry_id {
+            "cat" | "lion" | "tiger" => Silhouette::Feline,
+            "dog" | "fox" | "wolf" => Silhouette::Canine,
+            "rabbit" => Silhouette::Rabbit,
+            "horse" => Silhouette::Horse,
+            "deer" => Silhouette::Deer,
+            "dragon" => Silhouette::Dragon,
+            "snake" => Silhouette::Snake,
+            "bat" => Silhouette::Bat,
+            _ => Silhouette::Round,
+        }
+    }
+
+    /// Ears, horns or a hood, drawn before the head so it covers their base
+    fn ears(self, colors: &Colors) -> String {
+        let Colors { main, accent, .. } = colors;
+        match self {
+            Silhouette::Feline => format!(
+                r#"<path d="M19 26 L17 8 L30 19 Z M45 26 L47 8 L34 19 Z" fill="{main}"/><path d="M20.5 22 L19.5 12.5 L26.5 18.5 Z M43.5 22 L44.5 12.5 L37.5 18.5 Z" fill="{accent}"/>"#
+            ),
+            Silhouette::Canine => format!(
+                r#"<path d="M19 24 L15 3 L31 16 Z M45 24 L49 3 L33 16 Z" fill="{main}"/><path d="M20 20 L17.5 8.5 L27 16 Z M44 20 L46.5 8.5 L37 16 Z" fill="{accent}"/>"#
+            ),
+            Silhouette::Rabbit => format!(
+                r#"<ellipse cx="25" cy="12" rx="5" ry="13" transform="rotate(-10 25 12)" fill="{main}"/><ellipse cx="39" cy="12" rx="5" ry="13" transform="rotate(10 39 12)" fill="{main}"/><ellipse cx="25" cy="12" rx="2.5" ry="9" transform="rotate(-10 25 12)" fill="{accent}"/><ellipse cx="39" cy="12" rx="2.5" ry="9" transform="rotate(10 39 12)" fill="{accent}"/>"#
+            ),
+            Silhouette::Horse => format!(
+                r#"<path d="M23 19 L22 7 L29 15 Z M41 19 L42 7 L35 15 Z" fill="{main}"/><path d="M26 17 Q32 8 38 17 L36 22 Q32 17 28 22 Z" fill="{accent}"/>"#
+            ),
+            Silhouette::Deer => format!(
+                r#"<path d="M25 17 L20 4 M21.5 8 L15 6 M39 17 L44 4 M42.5 8 L49 6" stroke="{accent}" stroke-width="2.5" stroke-linecap="round" fill="none"/><ellipse cx="17" cy="22" rx="8" ry="3.5" transform="rotate(25 17 22)" fill="{main}"/><ellipse cx="47" cy="22" rx="8" ry="3.5" transform="rotate(-25 47 22)" fill="{main}"/>"#
+            ),
+            Silhouette::Dragon => format!(
+                r#"<path d="M23 21 Q16 10 11 3 Q21 9 28 17 Z M41 21 Q48 10 53 3 Q43 9 36 17 Z" fill="{accent}"/><path d="M28 18 L30 12 L32 17 L34 12 L36 18 Z" fill="{accent}"/>"#
+            ),
+            Silhouette::Snake => {
+                format!(r#"<ellipse cx="32" cy="36" rx="22" ry="19" fill="{accent}"/>"#)
+            }
+            Silhouette::Bat => format!(
+                r#"<path d="M20 26 L9 3 L31 18 Z M44 26 L55 3 L33 18 Z" fill="{main}"/><path d="M20 21 L13 9 L27 18 Z M44 21 L51 9 L37 18 Z" fill="{accent}"/>"#
+            ),
+            Silhouette::Round => format!(
+                r#"<circle cx="20" cy="19" r="6.5" fill="{main}"/><circle cx="44" cy="19" r="6.5" fill="{main}"/><circle cx="20" cy="19" r="3.5" fill="{accent}"/><circle cx="44" cy="19" r="3.5" fill="{accent}"/>"#
+            ),
+        }
+    }
+
+    /// The head with its muzzle, eyes and nose
+    fn head(self, colors: &Colors) -> String {
+        let Colors { main, accent, marking } = colors;
+        let (shape, muzzle, nose_y) = match self {
+            Silhouette::Feline | Silhouette::Bat | Silhouette::Round => (
+                r#"<ellipse cx="32" cy="31" rx="15" ry="14""#,
+                r#"<ellipse cx="32" cy="38" rx="7.5" ry="5""#,
+                36.0,
+            ),
+            Silhouette::Canine => (
+                r#"<ellipse cx="32" cy="30" rx="14" ry="15""#,
+                r#"<ellipse cx="32" cy="38" rx="8" ry="6.5""#,
+                35.5,
+            ),
+            Silhouette::Rabbit => (
+                r#"<circle cx="32" cy="33" r="14""#,
+                r#"<ellipse cx="32" cy="39" rx="6.5" ry="4.5""#,
+                37.0,
+            ),
+            Silhouette::Horse | Silhouette::Deer => (
+                r#"<ellipse cx="32" cy="32" rx="12" ry="17""#,
+                r#"<ellipse cx="32" cy="42" rx="9" ry="7""#,
+                41.0,
+            ),
+            Silhouette::Dragon => (
+                r#"<ellipse cx="32" cy="31" rx="15" ry="14""#,
+                r#"<ellipse cx="32" cy="39" rx="9" ry="5.5""#,
+                37.5,
+            ),
+            Silhouette::Snake => (
+                r#"<ellipse cx="32" cy="32" rx="16" ry="13""#,
+                r#"<ellipse cx="32" cy="38" rx="8" ry="4.5""#,
+                36.5,
+            ),
+        };
+
+        let eyes = match self {
+            Silhouette::Feline | Silhouette::Dragon | Silhouette::Snake => format!(
+                r#"<ellipse cx="26" cy="29" rx="2.8" ry="3" fill="{accent}"/><ellipse cx="38" cy="29" rx="2.8" ry="3" fill="{accent}"/><ellipse cx="26" cy="29" rx="0.8" ry="2.6" fill="{EYES}"/><ellipse cx="38" cy="29" rx="0.8" ry="2.6" fill="{EYES}"/>"#
+            ),
+            _ => format!(
+                r#"<circle cx="26" cy="29" r="2.3" fill="{EYES}"/><circle cx="38" cy="29" r="2.3" fill="{EYES}"/>"#
+            ),
+        };
+
+        format!(
+            r#"{shape} fill="{main}"/>{muzzle} fill="{marking}"/>{eyes}<ellipse cx="32" cy="{nose_y}" rx="2.5" ry="1.8" fill="{EYES}"/>"#
+        )
+    }
+}
+
+/// Where an accessory goes in the drawing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layer {
+    /// Behind the body and head
+    Back,
+    /// On top of everything
+    Front,
+}
+
+/// The accessory a characteristic entry asks for, or the default pack's for its id
+fn accessory_of<'a>(entry_id: &'a str, pools: &'a WordPools) -> Option<&'a str> {
+    pools
+        .entries(packs::CHARACTERISTIC)
+        .iter()
+        .find(|entry| entry.id == entry_id)
+        .and_then(|entry| entry.accessory.as_deref())
+        .or_else(|| default_accessory(entry_id))
+}
+
+/// For characteristics of the default pack, which doesn't spell its accessories out
+fn default_accessory(entry_id: &str) -> Option<&'static str> {
+    let name = match entry_id {
+        "mokawk" => "mohawk",
+        "earrings" => "earrings",
+        "always-cape" => "cape",
+        "ponytail" => "ponytail",
+        "hat-collection" => "top-hat",
+        "secret-nerd" => "glasses",
+        "philosopher" => "thought-bubble",
+        "tiny-squeak" => "speech-bubble",
+        "sings-everything" => "music-note",
+        "uses-emoji" => "emoji",
+        "collects-bad-jokes" => "clown-nose",
+        "overdramatic" => "tear",
+        "constant-waffles" => "waffle",
+        "sparkle" => "sparkles",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// A little something for the characteristic, by the name packs use for it
+fn accessory(name: &str, colors: &Colors) -> Option<(Layer, String)> {
+    let Colors { accent, .. } = colors;
+    let glyph = match name {
+        "mohawk" => (
+            Layer::Front,
+            format!(r#"<path d="M26 19 L27 7 L30.5 17 L32 4 L33.5 17 L37 7 L38 19 Z" fill="{accent}"/>"#),
+        ),
+        "earrings" => (
+            Layer::Front,
+            format!(
+                r#"<circle cx="17.5" cy="35" r="2.5" stroke="{GOLD}" stroke-width="1.5" fill="none"/><circle cx="46.5" cy="35" r="2.5" stroke="{GOLD}" stroke-width="1.5" fill="none"/>"#
+            ),
+        ),
+        "cape" => (
+            Layer::Back,
+            format!(
+                r#"<path d="M4 64 Q8 43 32 43 Q56 43 60 64 Z" fill="{accent}"/><circle cx="32" cy="48" r="2.5" fill="{GOLD}"/>"#
+            ),
+        ),
+        "ponytail" => (
+            Layer::Back,
+            format!(r#"<ellipse cx="49" cy="27" rx="5" ry="11" transform="rotate(25 49 27)" fill="{accent}"/>"#),
+        ),
+        "top-hat" => (
+            Layer::Front,
+            format!(
+                r#"<rect x="23" y="2" width="18" height="14" rx="1.5" fill="{EYES}"/><rect x="23" y="12" width="18" height="3" fill="{accent}"/><rect x="16" y="15" width="32" height="3.5" rx="1.5" fill="{EYES}"/>"#
+            ),
+        ),
+        "glasses" => (
+            Layer::Front,
+            format!(
+                r#"<circle cx="26" cy="29" r="5" stroke="{EYES}" stroke-width="1.5" fill="none"/><circle cx="38" cy="29" r="5" stroke="{EYES}" stroke-width="1.5" fill="none"/><path d="M31 29 L33 29" stroke="{EYES}" stroke-width="1.5"/>"#
+            ),
+        ),
+        "thought-bubble" => (
+            Layer::Front,
+            format!(
+                r#"<circle cx="53" cy="10" r="7" fill="white" stroke="{EYES}" stroke-width="1"/><circle cx="46" cy="20" r="2" fill="white" stroke="{EYES}" stroke-width="1"/><circle cx="43" cy="24" r="1.2" fill="white" stroke="{EYES}" stroke-width="1"/>"#
+            ),
+        ),
+        "speech-bubble" => (
+            Layer::Front,
+            format!(
+                r#"<path d="M49 4 H59 A3 3 0 0 1 62 7 V12 A3 3 0 0 1 59 15 H53 L50 18 V15 A3 3 0 0 1 46 12 V7 A3 3 0 0 1 49 4 Z" fill="white" stroke="{EYES}" stroke-width="1"/><circle cx="54" cy="9.5" r="1.5" fill="{EYES}"/>"#
+            ),
+        ),
+        "music-note" => (
+            Layer::Front,
+            format!(
+                r#"<path d="M54 4 V15 M54 4 Q58 5 59 9" stroke="{EYES}" stroke-width="1.5" fill="none"/><ellipse cx="51.5" cy="15.5" rx="3" ry="2.2" fill="{EYES}"/>"#
+            ),
+        ),
+        "emoji" => (
+            Layer::Front,
+            format!(
+                r#"<circle cx="53" cy="11" r="7" fill="{GOLD}"/><circle cx="50.5" cy="9.5" r="1" fill="{EYES}"/><circle cx="55.5" cy="9.5" r="1" fill="{EYES}"/><path d="M50 13 Q53 16 56 13" stroke="{EYES}" stroke-width="1" fill="none"/>"#
+            ),
+        ),
+        "clown-nose" => (
+            Layer::Front,
+            r##"<circle cx="32" cy="37" r="3.5" fill="#e0413a"/>"##.to_string(),
+        ),
+        "tear" => (
+            Layer::Front,
+            r##"<path d="M25 33 Q22 38 25 39 Q28 38 25 33 Z" fill="#7cc3f0"/>"##.to_string(),
+        ),
+        "waffle" => (
+            Layer::Front,
+            r##"<rect x="46" y="47" width="14" height="14" rx="2" fill="#d9a441"/><path d="M49.5 47 V61 M53 47 V61 M56.5 47 V61 M46 50.5 H60 M46 54 H60 M46 57.5 H60" stroke="#a8752a" stroke-width="0.8"/>"##.to_string(),
+        ),
+        "sparkles" => (
+            Layer::Front,
+            format!(
+                r#"<path d="M10 8 Q11 13 16 14 Q11 15 10 20 Q9 15 4 14 Q9 13 10 8 Z M54 42 Q54.7 45.3 58 46 Q54.7 46.7 54 50 Q53.3 46.7 50 46 Q53.3 45.3 54 42 Z" fill="{GOLD}"/>"#
+            ),
+        ),
+        _ => return None,
+    };
+    Some(glyph)
+}
+
+/// One of a few markings, picked by the avatar's rng
+fn marking(rng: &mut fastrand::Rng, colors: &Colors) -> String {
+    let Colors { marking, .. } = colors;
+    match rng.u8(..4) {
+        0 => format!(r#"<ellipse cx="32" cy="22" rx="3" ry="5" fill="{marking}"/>"#),
+        1 => format!(
+            r#"<path d="M17.5 30 L23 31 M17.5 34 L23 33.5 M46.5 30 L41 31 M46.5 34 L41 33.5" stroke="{marking}" stroke-width="1.8" stroke-linecap="round"/>"#
+        ),
+        2 => format!(r#"<ellipse cx="32" cy="56" rx="7" ry="6" fill="{marking}"/>"#),
+        _ => String::new(),
+    }
+}
+
+struct Colors {
+    main: String,
+    accent: String,
+    marking: String,
+}
+
+impl Colors {
+    fn of(palette: Option<&Palette>) -> Self {
+        let color = |role, fallback: &str| {
+            palette
+                .and_then(|palette| palette.swatches.iter().find(|swatch| swatch.role == role))
+                // Colors go into attributes as they are
+                .filter(|swatch| swatch.is_valid())
+                .map_or_else(|| fallback.to_string(), |swatch| swatch.hex.clone())
+        };
+        Self {
+            main: color(SwatchRole::Main, FALLBACK_MAIN),
+            accent: color(SwatchRole::Accent, FALLBACK_ACCENT),
+            marking: color(SwatchRole::Marking, FALLBACK_MARKING),
+        }
+    }
+}
+
+/// FNV-1a, unlike the std hasher it's guaranteed to stay the same between Rust versions
+fn stable_hash(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes.into_iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// The avatar for a character's traits and palette, as SVG for showing with `widget::svg`
+///
+/// The pools only say which silhouette and accessory entries ask for, not what a seed rolls.
+pub fn svg(traits: &[OcTrait], palette: Option<&Palette>, pools: &WordPools) -> Vec<u8> {
+    let entry = |slot| {
+        traits
+            .iter()
+            .find(|t| t.slot == slot)
+            .map(|t| t.entry_id.as_str())
+            .unwrap_or_default()
+    };
+    let species = traits.iter().find(|t| t.slot == TraitSlot::Species);
+    // A hybrid's head comes from the first parent and its ears from the second
+    let (head, ears) = match species.map(|species| species.parents.as_slice()) {
+        Some([first, second, ..]) => (
+            Silhouette::of(&first.entry_id, pools),
+            Silhouette::of(&second.entry_id, pools),
+        ),
+        _ => {
+            let silhouette = Silhouette::of(entry(TraitSlot::Species), pools);
+            (silhouette, silhouette)
+        }
+    };
+
+    let colors = Colors::of(palette);
+    let mut rng = fastrand::Rng::with_seed(stable_hash(
+        traits.iter().flat_map(|t| t.seed.to_le_bytes()),
+    ));
+    let accessory = accessory_of(entry(TraitSlot::Characteristic), pools)
+        .and_then(|name| accessory(name, &colors));
+    let layer = |wanted| {
+        accessory
+            .as_ref()
+            .filter(|(layer, _)| *layer == wanted)
+            .map(|(_, glyph)| glyph.as_str())
+            .unwrap_or_default()
+    };
+
+    let size = SIZE;
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}">"#
+    );
+    svg.push_str(&format!(
+        r#"<circle cx="32" cy="32" r="32" fill="{}" fill-opacity="0.3"/>"#,
+        colors.accent
+    ));
+    svg.push_str(layer(Layer::Back));
+    svg.push_str(&format!(
+        r#"<path d="M10 64 Q12 46 32 46 Q52 46 54 64 Z" fill="{}"/>"#,
+        colors.main
+    ));
+    svg.push_str(&ears.ears(&colors));
+    svg.push_str(&head.head(&colors));
+    svg.push_str(&marking(&mut rng, &colors));
+    svg.push_str(layer(Layer::Front));
+    svg.push_str("</svg>");
+    svg.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::oc_generator::palette::{Harmony, Swatch};
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    fn traits(species: &str, characteristic: &str) -> Vec<OcTrait> {
+        [(TraitSlot::Species, species), (TraitSlot::Characteristic, characteristic)]
+            .map(|(slot, entry_id)| OcTrait {
+                slot,
+                entry_id: entry_id.to_string(),
+                text: entry_id.to_string(),
+                seed: 7,
+                parents: vec![],
+                features: BTreeMap::new(),
+            })
+            .to_vec()
+    }
+
+    fn avatar(species: &str, characteristic: &str, pools: &WordPools) -> Vec<u8> {
+        svg(&traits(species, characteristic), None, pools)
+    }
+
+    #[test]
+    fn packs_pick_silhouettes_and_accessories() {
+        let source = r#"
+            id = "custom"
+            name = "Custom"
+            [categories]
+            species = [{ id = "otter", text = "otter", silhouette = "rabbit" }]
+            characteristic = [{ id = "bookworm", text = "reads a lot", accessory = "glasses" }]
+        "#;
+        let pack = packs::parse_pack(Path::new("custom.toml"), source).unwrap();
+        let pools = WordPools::merge(&[pack], &[]);
+
+        assert_eq!(avatar("otter", "bookworm", &pools), avatar("rabbit", "secret-nerd", &pools));
+        assert_ne!(avatar("otter", "bookworm", &pools), avatar("otter", "", &pools));
+        // Unknown species without a silhouette are round
+        assert_ne!(avatar("otter", "", &pools), avatar("ghost", "", &pools));
+        assert_eq!(avatar("ghost", "", &pools), avatar("blob", "", &WordPools::default()));
+    }
+
+    #[test]
+    fn bad_colors_fall_back() {
+        let palette = Palette {
+            harmony: Harmony::Complementary,
+            swatches: vec![Swatch {
+                role: SwatchRole::Main,
+                hex: r#"red"/><script/>"#.to_string(),
+            }],
+        };
+        let svg = String::from_utf8(svg(&[], Some(&palette), &WordPools::default())).unwrap();
+        assert!(!svg.contains("<script"));
+        assert!(svg.contains(FALLBACK_MAIN));
+    }
+}
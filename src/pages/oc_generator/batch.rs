@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Batch generation
+//!
+//! Brainstorming goes faster with a whole grid of OCs to pick from than with one "Generate!"
+//! click per idea. Candidates are rolled just like the main OC, locks included, so locking a
+//! species and rolling a batch gives a grid of that species.
+
+use super::character::GeneratedOC;
+
+/// Batch sizes offered in the dropdown
+pub const SIZES: [u32; 3] = [5, 10, 50];
+
+/// One OC in the batch grid
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub oc: GeneratedOC,
+    /// Already in the favorites, so saving it again would only make a duplicate
+    pub saved: bool,
+}
+
+impl Candidate {
+    pub fn new(oc: GeneratedOC) -> Self {
+        Self { oc, saved: false }
+    }
+}
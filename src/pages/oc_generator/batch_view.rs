@@ -0,0 +1,101 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The batch grid
+//!
+//! Candidates from the last batch as small cards, each one saved on its own or ticked and
+//! saved together.
+
+use super::{swatches, Message, OcGeneratorPage};
+use crate::fl;
+use cosmic::iced::alignment::Vertical;
+use cosmic::iced::Length;
+use cosmic::widget::{self, column, icon, row, text};
+use cosmic::{cosmic_theme, prelude::*, theme};
+
+impl OcGeneratorPage {
+    /// The last batch as a grid of small cards
+    pub(super) fn batch_grid(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_xs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let cards = self
+            .batch
+            .iter()
+            .enumerate()
+            .map(|(index, candidate)| {
+                let save_button = widget::button::icon(icon::from_name("document-save-symbolic"));
+                let save_button = if candidate.saved {
+                    save_button
+                } else {
+                    save_button.on_press(Message::SaveCandidate(index))
+                };
+
+                column::column()
+                    .spacing(space_xxs)
+                    .push(
+                        widget::button::text(candidate.oc.text(self.pools.grammar()))
+                            .on_press(Message::ShowCandidate(index)),
+                    )
+                    .push_maybe(candidate.oc.palette.as_ref().map(swatches))
+                    .push(
+                        row()
+                            .spacing(space_xxs)
+                            .align_y(Vertical::Center)
+                            .push(
+                                widget::checkbox("", self.batch_selection.contains(&index))
+                                    .on_toggle(move |selected| {
+                                        Message::CandidateSelectionToggled(index, selected)
+                                    }),
+                            )
+                            .push_maybe(candidate.saved.then(|| text::caption(fl!("saved"))))
+                            .push(widget::horizontal_space())
+                            .push(
+                                widget::button::icon(icon::from_name("view-refresh-symbolic"))
+                                    .on_press(Message::RerollCandidate(index)),
+                            )
+                            .push(save_button),
+                    )
+                    .padding(space_xs)
+                    .width(Length::Fixed(280.0))
+                    .apply(widget::container)
+                    .class(theme::Container::Card)
+                    .into()
+            })
+            .collect::<Vec<Element<'_, Message>>>();
+
+        let save_selected = widget::button::standard(fl!(
+            "save-selected-button",
+            count = self.batch_selection.len()
+        ));
+        let save_selected = if self.batch_selection.is_empty() {
+            save_selected
+        } else {
+            save_selected.on_press(Message::SaveSelectedCandidates)
+        };
+
+        column::column()
+            .spacing(space_s)
+            .push(
+                row()
+                    .spacing(space_s)
+                    .align_y(Vertical::Center)
+                    .push(text::heading(fl!("batch", count = self.batch.len())))
+                    .push(widget::horizontal_space())
+                    .push(save_selected.class(theme::Button::Suggested))
+                    .push(
+                        widget::button::icon(icon::from_name("window-close-symbolic"))
+                            .on_press(Message::CloseBatch),
+                    ),
+            )
+            .push(
+                widget::scrollable(
+                    widget::flex_row(cards)
+                        .row_spacing(space_xs)
+                        .column_spacing(space_xs),
+                )
+                .height(Length::Fixed(320.0)),
+            )
+            .into()
+    }
+}
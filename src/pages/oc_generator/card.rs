@@ -0,0 +1,272 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Reference cards for saved OCs
+//!
+//! A card is a plain SVG document built from the character sheet and palette, so it renders the
+//! same everywhere. PNGs are rasterized on the CPU with resvg, which needs no GPU and no window,
+//! so the command line can make cards too (see `cli.rs`).
+
+use super::character::SheetField;
+use super::palette::Swatch;
+use super::SavedOC;
+use crate::fl;
+use resvg::{tiny_skia, usvg};
+
+const WIDTH: f32 = 640.0;
+const PADDING: f32 = 32.0;
+/// Roughly how many characters fit on a body text line
+const WRAP_AT: usize = 64;
+const LINE_HEIGHT: f32 = 22.0;
+/// Fonts to try in order, the first few are what COSMIC and most distros ship
+const FONTS: &str = "'Open Sans', 'Fira Sans', 'Noto Sans', 'DejaVu Sans', sans-serif";
+
+const BACKGROUND: &str = "#1E1E24";
+const TEXT: &str = "#F2F2F5";
+const MUTED: &str = "#A8A8B3";
+
+/// PNGs are rendered at twice the SVG size so they stay sharp on high density screens
+const PNG_SCALE: f32 = 2.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardFormat {
+    Svg,
+    Png,
+}
+
+impl CardFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            CardFormat::Svg => "svg",
+            CardFormat::Png => "png",
+        }
+    }
+}
+
+/// Render a card in the given format
+pub fn render(character: &SavedOC, format: CardFormat) -> Result<Vec<u8>, String> {
+    let svg = render_svg(character);
+    match format {
+        CardFormat::Svg => Ok(svg.into_bytes()),
+        CardFormat::Png => svg_to_png(&svg),
+    }
+}
+
+/// The card as an SVG document
+pub fn render_svg(character: &SavedOC) -> String {
+    let sheet = &character.sheet;
+    // Colors go into attributes as they are, so anything that isn't #RRGGBB is left out
+    let swatches: Vec<&Swatch> = character
+        .palette
+        .iter()
+        .flat_map(|palette| &palette.swatches)
+        .filter(|swatch| swatch.is_valid())
+        .collect();
+    let main_color = swatches
+        .first()
+        .map(|s| s.hex.as_str())
+        .unwrap_or("#7A5AF8");
+    let accent_color = swatches
+        .get(1)
+        .map(|s| s.hex.as_str())
+        .unwrap_or(main_color);
+
+    let mut body = String::new();
+    let mut y = PADDING + 32.0;
+
+    body.push_str(&text_element(PADDING, y, 32.0, "bold", TEXT, sheet.title()));
+    y += 30.0;
+
+    let subtitle: Vec<&str> = [&sheet.species, &sheet.pronouns, &sheet.age]
+        .into_iter()
+        .map(String::as_str)
+        .filter(|value| !value.is_empty())
+        .collect();
+    if !subtitle.is_empty() {
+        body.push_str(&text_element(
+            PADDING,
+            y,
+            16.0,
+            "normal",
+            MUTED,
+            &subtitle.join(" · "),
+        ));
+        y += 16.0;
+    }
+
+    for field in [
+        SheetField::Attributes,
+        SheetField::Quirks,
+        SheetField::Backstory,
+        SheetField::Description,
+    ] {
+        let value = sheet.field(field);
+        if value.is_empty() {
+            continue;
+        }
+
+        y += 28.0;
+        body.push_str(&text_element(
+            PADDING,
+            y,
+            12.0,
+            "bold",
+            accent_color,
+            &field.label().to_uppercase(),
+        ));
+        for line in wrap(&value, WRAP_AT) {
+            y += LINE_HEIGHT;
+            body.push_str(&text_element(PADDING, y, 15.0, "normal", TEXT, &line));
+        }
+    }
+
+    if !swatches.is_empty() {
+        y += 24.0;
+        for (index, swatch) in swatches.iter().enumerate() {
+            let x = PADDING + index as f32 * 112.0;
+            body.push_str(&format!(
+                r##"<rect x="{x}" y="{y}" width="40" height="40" rx="10" fill="{}" stroke="#FFFFFF33"/>"##,
+                swatch.hex
+            ));
+            body.push_str(&text_element(
+                x + 48.0,
+                y + 17.0,
+                12.0,
+                "bold",
+                TEXT,
+                &swatch.role.label(),
+            ));
+            body.push_str(&text_element(
+                x + 48.0,
+                y + 34.0,
+                12.0,
+                "normal",
+                MUTED,
+                &swatch.hex,
+            ));
+        }
+        y += 40.0;
+    }
+
+    // Branding and tags along the bottom
+    y += 40.0;
+    if !character.tags.is_empty() {
+        let tags: Vec<String> = character.tags.iter().map(|tag| format!("#{tag}")).collect();
+        body.push_str(&text_element(
+            PADDING,
+            y,
+            12.0,
+            "normal",
+            MUTED,
+            &tags.join(" "),
+        ));
+    }
+    body.push_str(&format!(
+        r#"<text x="{}" y="{y}" font-family="{FONTS}" font-size="12" font-weight="bold" fill="{MUTED}" text-anchor="end">{}</text>"#,
+        WIDTH - PADDING,
+        escape(&fl!("card-branding"))
+    ));
+    let height = y + PADDING;
+
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}">
+<clipPath id="card"><rect width="{WIDTH}" height="{height}" rx="24"/></clipPath>
+<g clip-path="url(#card)">
+<rect width="{WIDTH}" height="{height}" fill="{BACKGROUND}"/>
+<rect width="{WIDTH}" height="8" fill="{main_color}"/>
+<rect y="8" width="{WIDTH}" height="4" fill="{accent_color}"/>
+</g>
+{body}
+</svg>
+"#
+    )
+}
+
+/// Rasterize an SVG on the CPU
+pub fn svg_to_png(svg: &str) -> Result<Vec<u8>, String> {
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+
+    let tree = usvg::Tree::from_str(svg, &options).map_err(|e| e.to_string())?;
+    let size = tree
+        .size()
+        .to_int_size()
+        .scale_by(PNG_SCALE)
+        .ok_or("card is too big")?;
+    let mut pixmap =
+        tiny_skia::Pixmap::new(size.width(), size.height()).ok_or("card is too big")?;
+
+    resvg::render(
+        &tree,
+        tiny_skia::Transform::from_scale(PNG_SCALE, PNG_SCALE),
+        &mut pixmap.as_mut(),
+    );
+    pixmap.encode_png().map_err(|e| e.to_string())
+}
+
+fn text_element(x: f32, y: f32, size: f32, weight: &str, fill: &str, text: &str) -> String {
+    format!(
+        r#"<text x="{x}" y="{y}" font-family="{FONTS}" font-size="{size}" font-weight="{weight}" fill="{fill}">{}</text>"#,
+        escape(text)
+    )
+}
+
+/// SVG has no automatic line wrapping, so break on spaces ourselves
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + word.chars().count() + 1 > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::oc_generator::character::CharacterSheet;
+    use crate::pages::oc_generator::palette::{Harmony, Palette, SwatchRole};
+
+    #[test]
+    fn bad_colors_are_left_out() {
+        let sheet = CharacterSheet {
+            name: "Ash & <Co>".to_string(),
+            ..Default::default()
+        };
+        let mut character = SavedOC::new(1, sheet);
+        character.palette = Some(Palette {
+            harmony: Harmony::Complementary,
+            swatches: vec![
+                Swatch {
+                    role: SwatchRole::Main,
+                    hex: r#"red"/><script/>"#.to_string(),
+                },
+                Swatch {
+                    role: SwatchRole::Accent,
+                    hex: "#FF8800".to_string(),
+                },
+            ],
+        });
+
+        let svg = render_svg(&character);
+        assert!(!svg.contains("<script"));
+        assert!(svg.contains("Ash &amp; &lt;Co&gt;"));
+        assert!(svg.contains(r##"fill="#FF8800""##));
+        assert!(usvg::Tree::from_str(&svg, &usvg::Options::default()).is_ok());
+    }
+}
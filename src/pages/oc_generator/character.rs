@@ -0,0 +1,306 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Generated characters
+//!
+//! A generated OC is kept as typed slots instead of one big string so single traits can be
+//! locked while the rest get rerolled. Every trait remembers the seed it was rolled from, so the
+//! whole character can be rebuilt from its seeds (plus the packs that were enabled).
+
+use super::grammar::{self, Grammar};
+use super::palette::Palette;
+use super::{packs, seed};
+use crate::fl;
+use crate::i18n::{strip_isolation_marks, LANGUAGE_LOADER};
+use std::collections::{BTreeMap, HashMap};
+
+/// The parts of an OC that get rolled (and locked) independently
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum TraitSlot {
+    Attribute,
+    Species,
+    Characteristic,
+    Name,
+    Pronouns,
+    Age,
+    Backstory,
+}
+
+impl TraitSlot {
+    /// Every slot, in the order they are rolled and shown
+    ///
+    /// New slots go at the end so older seed codes still roll the same first traits
+    pub const ALL: [TraitSlot; 7] = [
+        TraitSlot::Attribute,
+        TraitSlot::Species,
+        TraitSlot::Characteristic,
+        TraitSlot::Name,
+        TraitSlot::Pronouns,
+        TraitSlot::Age,
+        TraitSlot::Backstory,
+    ];
+
+    /// The pack category this slot picks from
+    pub fn category(self) -> &'static str {
+        match self {
+            TraitSlot::Attribute => packs::ATTRIBUTE,
+            TraitSlot::Species => packs::SPECIES,
+            TraitSlot::Characteristic => packs::CHARACTERISTIC,
+            TraitSlot::Name => packs::NAME,
+            TraitSlot::Pronouns => packs::PRONOUNS,
+            TraitSlot::Age => packs::AGE,
+            TraitSlot::Backstory => packs::BACKSTORY,
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            TraitSlot::Attribute => fl!("slot-attribute"),
+            TraitSlot::Species => fl!("slot-species"),
+            TraitSlot::Characteristic => fl!("slot-characteristic"),
+            TraitSlot::Name => fl!("slot-name"),
+            TraitSlot::Pronouns => fl!("slot-pronouns"),
+            TraitSlot::Age => fl!("slot-age"),
+            TraitSlot::Backstory => fl!("slot-backstory"),
+        }
+    }
+}
+
+/// One rolled trait of an OC
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OcTrait {
+    pub slot: TraitSlot,
+    /// Id of the pack entry that was picked, stable across languages
+    pub entry_id: String,
+    /// The entry's text in the language it was generated in
+    pub text: String,
+    /// Seed this trait was rolled from
+    pub seed: u64,
+    /// Both species a hybrid species trait was made from, empty for everything else
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parents: Vec<ParentSpecies>,
+    /// Grammatical features of the entry in the language it was generated in, like `gender`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub features: BTreeMap<String, String>,
+}
+
+/// One of the species a hybrid was rolled from
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ParentSpecies {
+    pub entry_id: String,
+    pub text: String,
+}
+
+/// An OC as structured traits, with the slots the user locked
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct GeneratedOC {
+    pub traits: Vec<OcTrait>,
+    pub locked: Vec<TraitSlot>,
+    /// Colors rolled from the species trait, so they stay put while the species is locked
+    pub palette: Option<Palette>,
+}
+
+impl GeneratedOC {
+    pub fn get(&self, slot: TraitSlot) -> Option<&OcTrait> {
+        self.traits.iter().find(|t| t.slot == slot)
+    }
+
+    pub fn is_locked(&self, slot: TraitSlot) -> bool {
+        self.locked.contains(&slot)
+    }
+
+    pub fn toggle_lock(&mut self, slot: TraitSlot) {
+        if self.is_locked(slot) {
+            self.locked.retain(|locked| *locked != slot);
+        } else {
+            self.locked.push(slot);
+        }
+    }
+
+    /// The OC as a sentence, from the locale's `oc-sentence` message
+    ///
+    /// Every trait is passed to the message under its pack category (`$species`), along with
+    /// its grammatical features (`$species-gender`), so each locale builds the whole sentence
+    /// with its own articles and agreement. The result is then expanded as a grammar template,
+    /// so the sentence can still use `#rule#`s from packs, which can use the traits in turn.
+    pub fn text(&self, grammar: &Grammar) -> String {
+        let mut grammar = grammar.clone();
+        let mut args: HashMap<String, String> = HashMap::new();
+        for oc_trait in &self.traits {
+            let category = oc_trait.slot.category();
+            grammar.set_rule(category, vec![oc_trait.text.clone()]);
+            args.insert(category.to_string(), oc_trait.text.clone());
+            for (feature, value) in &oc_trait.features {
+                args.insert(format!("{category}-{feature}"), value.clone());
+            }
+            // Entries only need to set their English article when the guess gets it wrong
+            if !oc_trait.features.contains_key("article") {
+                args.insert(format!("{category}-article"), grammar::article(&oc_trait.text).to_string());
+            }
+        }
+        let sentence = strip_isolation_marks(&LANGUAGE_LOADER.get_args("oc-sentence", args));
+
+        // Pack rules can have several alternatives, so seed those picks too
+        let mut rng = fastrand::Rng::with_seed(self.traits.first().map(|t| t.seed).unwrap_or_default());
+        grammar.expand(&sentence, &mut rng)
+    }
+
+    /// Fill in a character sheet from the rolled traits
+    pub fn to_sheet(&self, grammar: &Grammar) -> CharacterSheet {
+        let text_of = |slot| self.get(slot).map(|t| t.text.clone()).unwrap_or_default();
+        let list_of = |slot| self.get(slot).map(|t| vec![t.text.clone()]).unwrap_or_default();
+
+        CharacterSheet {
+            name: text_of(TraitSlot::Name),
+            pronouns: text_of(TraitSlot::Pronouns),
+            age: text_of(TraitSlot::Age),
+            species: text_of(TraitSlot::Species),
+            parent_species: self
+                .get(TraitSlot::Species)
+                .map(|species| species.parents.iter().map(|p| p.text.clone()).collect())
+                .unwrap_or_default(),
+            attributes: list_of(TraitSlot::Attribute),
+            quirks: list_of(TraitSlot::Characteristic),
+            backstory: text_of(TraitSlot::Backstory),
+            description: self.text(grammar),
+            notes: String::new(),
+        }
+    }
+
+    /// The seed every trait was rolled from, if they all share one
+    pub fn shared_seed(&self) -> Option<u64> {
+        let first = self.traits.first()?.seed;
+        self.traits.iter().all(|t| t.seed == first).then_some(first)
+    }
+
+    /// Shareable code for this OC
+    ///
+    /// A single seed code when every trait shares a seed, otherwise one code per slot joined
+    /// with dashes (which happens after rerolling with locked traits). Slots missing from the
+    /// end of the code use the first seed, so trailing repeats of it are left off.
+    pub fn seed_code(&self) -> String {
+        let mut seeds: Vec<u64> = self.traits.iter().map(|t| t.seed).collect();
+        while seeds.len() > 1 && seeds.last() == seeds.first() {
+            seeds.pop();
+        }
+
+        seed::encode_all(&seeds)
+    }
+}
+
+/// Everything we know about a character, generated or typed in by the user
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct CharacterSheet {
+    pub name: String,
+    pub pronouns: String,
+    /// Free text so "centuries old" works as well as "27"
+    pub age: String,
+    pub species: String,
+    /// What a hybrid species is a mix of, so filters find a folf under "fox" and "wolf"
+    pub parent_species: Vec<String>,
+    pub attributes: Vec<String>,
+    pub quirks: Vec<String>,
+    /// A short hook to build a story around
+    pub backstory: String,
+    /// The generated sentence, or the whole text of OCs saved before sheets existed
+    pub description: String,
+    pub notes: String,
+}
+
+/// The fields of a character sheet, so the edit form can share one message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SheetField {
+    Name,
+    Pronouns,
+    Age,
+    Species,
+    ParentSpecies,
+    Attributes,
+    Quirks,
+    Backstory,
+    Description,
+    Notes,
+}
+
+impl SheetField {
+    pub const ALL: [SheetField; 10] = [
+        SheetField::Name,
+        SheetField::Pronouns,
+        SheetField::Age,
+        SheetField::Species,
+        SheetField::ParentSpecies,
+        SheetField::Attributes,
+        SheetField::Quirks,
+        SheetField::Backstory,
+        SheetField::Description,
+        SheetField::Notes,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            SheetField::Name => fl!("sheet-name"),
+            SheetField::Pronouns => fl!("sheet-pronouns"),
+            SheetField::Age => fl!("sheet-age"),
+            SheetField::Species => fl!("sheet-species"),
+            SheetField::ParentSpecies => fl!("sheet-parent-species"),
+            SheetField::Attributes => fl!("sheet-attributes"),
+            SheetField::Quirks => fl!("sheet-quirks"),
+            SheetField::Backstory => fl!("sheet-backstory"),
+            SheetField::Description => fl!("sheet-description"),
+            SheetField::Notes => fl!("sheet-notes"),
+        }
+    }
+}
+
+impl CharacterSheet {
+    /// A field as display text, lists joined with commas
+    pub fn field(&self, field: SheetField) -> String {
+        match field {
+            SheetField::Name => self.name.clone(),
+            SheetField::Pronouns => self.pronouns.clone(),
+            SheetField::Age => self.age.clone(),
+            SheetField::Species => self.species.clone(),
+            SheetField::ParentSpecies => self.parent_species.join(", "),
+            SheetField::Attributes => self.attributes.join(", "),
+            SheetField::Quirks => self.quirks.join(", "),
+            SheetField::Backstory => self.backstory.clone(),
+            SheetField::Description => self.description.clone(),
+            SheetField::Notes => self.notes.clone(),
+        }
+    }
+
+    /// Update a field from the edit form, lists are split on commas
+    pub fn set_field(&mut self, field: SheetField, value: String) {
+        let split = |value: &str| {
+            value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(String::from)
+                .collect()
+        };
+
+        match field {
+            SheetField::Name => self.name = value,
+            SheetField::Pronouns => self.pronouns = value,
+            SheetField::Age => self.age = value,
+            SheetField::Species => self.species = value,
+            SheetField::ParentSpecies => self.parent_species = split(&value),
+            SheetField::Attributes => self.attributes = split(&value),
+            SheetField::Quirks => self.quirks = split(&value),
+            SheetField::Backstory => self.backstory = value,
+            SheetField::Description => self.description = value,
+            SheetField::Notes => self.notes = value,
+        }
+    }
+
+    /// Name if there is one, otherwise the description, for lists and titles
+    pub fn title(&self) -> &str {
+        if self.name.is_empty() {
+            &self.description
+        } else {
+            &self.name
+        }
+    }
+}
@@ -0,0 +1,287 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Commission sheets
+//!
+//! A structured brief to hand an artist: species, build, palette, distinguishing features and
+//! empty slots for do/don't notes and reference images. Sheets are made from templates with
+//! `{{placeholder}}`s in them. The defaults are bundled, and "Edit templates" copies them next
+//! to the app's config so they can be changed in any text editor.
+
+use super::character::{SheetField, TraitSlot};
+use super::packs::{self, WordPools};
+use super::palette::SwatchRole;
+use super::SavedOC;
+use crate::config::CONFIG_KEY;
+use crate::fl;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Template lines starting with this are notes for whoever edits the template
+const COMMENT: &str = ";;";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommissionFormat {
+    Markdown,
+    PlainText,
+}
+
+impl CommissionFormat {
+    pub const ALL: [CommissionFormat; 2] = [CommissionFormat::Markdown, CommissionFormat::PlainText];
+
+    pub fn label(self) -> String {
+        match self {
+            CommissionFormat::Markdown => fl!("export-commission-md-button"),
+            CommissionFormat::PlainText => fl!("export-commission-txt-button"),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            CommissionFormat::Markdown => "md",
+            CommissionFormat::PlainText => "txt",
+        }
+    }
+
+    fn default_template(self) -> &'static str {
+        match self {
+            CommissionFormat::Markdown => include_str!("../../../resources/templates/commission.md"),
+            CommissionFormat::PlainText => include_str!("../../../resources/templates/commission.txt"),
+        }
+    }
+
+    fn template_name(self) -> String {
+        format!("commission.{}", self.extension())
+    }
+}
+
+/// Where editable templates live, next to the cosmic config for the app
+pub fn template_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("cosmic").join(CONFIG_KEY).join("templates"))
+}
+
+/// The user's template for a format, or the bundled one if they haven't got one
+pub fn template(format: CommissionFormat) -> String {
+    template_dir()
+        .and_then(|dir| std::fs::read_to_string(dir.join(format.template_name())).ok())
+        .unwrap_or_else(|| format.default_template().to_string())
+}
+
+/// Copy the bundled templates to the template folder for editing, keeping any already there
+pub fn write_default_templates() -> Result<PathBuf, String> {
+    let dir = template_dir().ok_or_else(|| fl!("no-config-dir"))?;
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    for format in CommissionFormat::ALL {
+        let path = dir.join(format.template_name());
+        if !path.exists() {
+            std::fs::write(&path, format.default_template()).map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(dir)
+}
+
+/// Fill in a template for a character
+///
+/// Unknown placeholders are kept as they are so a typo shows up in the sheet. A line with
+/// placeholders that all came out empty is left out, so an OC without a palette doesn't get
+/// a dangling "Markings:" line. The pools tell build attributes from personality ones and
+/// should include switched off packs, a character may have been rolled from one.
+pub fn render(
+    character: &SavedOC,
+    format: CommissionFormat,
+    template: &str,
+    pools: &WordPools,
+) -> String {
+    let values = placeholders(character, format, pools);
+    let mut sheet = String::new();
+
+    for line in template.lines().filter(|line| !line.starts_with(COMMENT)) {
+        let mut rendered = String::new();
+        let mut rest = line;
+        let (mut any, mut filled) = (false, false);
+
+        while let Some(start) = rest.find("{{") {
+            let Some(length) = rest[start..].find("}}") else {
+                break;
+            };
+            let placeholder = &rest[start..start + length + 2];
+            rendered.push_str(&rest[..start]);
+            match values.get(placeholder[2..length].trim()) {
+                Some(value) => {
+                    any = true;
+                    filled |= !value.is_empty();
+                    rendered.push_str(value);
+                }
+                None => rendered.push_str(placeholder),
+            }
+            rest = &rest[start + length + 2..];
+        }
+        rendered.push_str(rest);
+
+        if !any || filled {
+            sheet.push_str(&rendered);
+            sheet.push('\n');
+        }
+    }
+
+    sheet
+}
+
+/// Whether an attribute from the sheet describes a body rather than a personality
+///
+/// Goes by the sheet's text rather than the rolled traits, since the sheet may have been edited
+/// since. The text is matched to a rolled trait first, since that's in the language it was
+/// rolled in, then to the pack entries in the current language. Anything that isn't a pack
+/// entry marked `build` counts as personality.
+fn is_build(attribute: &str, character: &SavedOC, pools: &WordPools) -> bool {
+    let attribute = attribute.to_lowercase();
+    let builds = || pools.entries(packs::ATTRIBUTE).iter().filter(|entry| entry.build);
+
+    character
+        .traits
+        .iter()
+        .filter(|t| t.slot == TraitSlot::Attribute && t.text.to_lowercase() == attribute)
+        .any(|t| builds().any(|entry| entry.id == t.entry_id))
+        || builds().any(|entry| entry.label().to_lowercase() == attribute)
+}
+
+fn placeholders(
+    character: &SavedOC,
+    format: CommissionFormat,
+    pools: &WordPools,
+) -> HashMap<&'static str, String> {
+    let sheet = &character.sheet;
+    let (build, personality): (Vec<_>, Vec<_>) = sheet
+        .attributes
+        .iter()
+        .map(String::as_str)
+        .partition(|attribute| is_build(attribute, character, pools));
+
+    let color = |role| {
+        character
+            .palette
+            .iter()
+            .flat_map(|palette| &palette.swatches)
+            .find(|swatch| swatch.role == role)
+            .map(|swatch| swatch.hex.clone())
+            .unwrap_or_default()
+    };
+    let palette = character
+        .palette
+        .iter()
+        .flat_map(|palette| &palette.swatches)
+        .map(|swatch| match format {
+            CommissionFormat::Markdown => format!("- **{}:** `{}`", swatch.role.label(), swatch.hex),
+            CommissionFormat::PlainText => format!("  {}: {}", swatch.role.label(), swatch.hex),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    HashMap::from([
+        ("name", sheet.title().to_string()),
+        ("pronouns", sheet.field(SheetField::Pronouns)),
+        ("age", sheet.field(SheetField::Age)),
+        ("species", sheet.field(SheetField::Species)),
+        ("parent-species", sheet.field(SheetField::ParentSpecies)),
+        ("build", build.join(", ")),
+        ("personality", personality.join(", ")),
+        ("features", sheet.field(SheetField::Quirks)),
+        ("backstory", sheet.field(SheetField::Backstory)),
+        ("description", sheet.field(SheetField::Description)),
+        ("notes", sheet.field(SheetField::Notes)),
+        ("tags", character.tags.join(", ")),
+        ("palette", palette),
+        ("main", color(SwatchRole::Main)),
+        ("accent", color(SwatchRole::Accent)),
+        ("marking", color(SwatchRole::Marking)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::oc_generator::character::{CharacterSheet, OcTrait};
+    use crate::pages::oc_generator::palette::{Harmony, Palette, Swatch};
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    fn pools() -> WordPools {
+        let default = include_str!("../../../resources/packs/default.toml");
+        let custom = r#"
+            id = "custom"
+            name = "Custom"
+            [categories]
+            attribute = [{ id = "hulking", text = "hulking", build = true }]
+        "#;
+        let packs = [
+            packs::parse_pack(Path::new("default.toml"), default).unwrap(),
+            packs::parse_pack(Path::new("custom.toml"), custom).unwrap(),
+        ];
+        WordPools::merge(&packs, &[])
+    }
+
+    fn character() -> SavedOC {
+        let sheet = CharacterSheet {
+            name: "Ash".to_string(),
+            attributes: vec!["Tall".to_string(), "shy".to_string()],
+            ..Default::default()
+        };
+        SavedOC::new(1, sheet)
+    }
+
+    #[test]
+    fn fills_placeholders_and_drops_empty_lines() {
+        let template = "Name: {{ name }}\nMarkings: {{marking}}\n;; note to self\n\nBuild: {{build}}\n\
+                        Personality: {{personality}}\n{{name}} {{marking}}";
+        let sheet = render(&character(), CommissionFormat::Markdown, template, &pools());
+        assert_eq!(sheet, "Name: Ash\n\nBuild: Tall\nPersonality: shy\nAsh \n");
+    }
+
+    #[test]
+    fn build_attributes_come_from_packs_and_traits() {
+        let mut character = character();
+        character.sheet.attributes = ["riesig", "hulking", "shy", "tall"].map(String::from).to_vec();
+        // Rolled in another language, the sheet has the text it was rolled with
+        character.traits.push(OcTrait {
+            slot: TraitSlot::Attribute,
+            entry_id: "tall".to_string(),
+            text: "Riesig".to_string(),
+            seed: 0,
+            parents: vec![],
+            features: BTreeMap::new(),
+        });
+
+        let template = "{{build}}\n{{personality}}";
+        let sheet = render(&character, CommissionFormat::PlainText, template, &pools());
+        assert_eq!(sheet, "riesig, hulking, tall\nshy\n");
+    }
+
+    #[test]
+    fn keeps_unknown_placeholders() {
+        let template = "{{nmae}} is {{name}}\nOops {{name";
+        let sheet = render(&character(), CommissionFormat::PlainText, template, &pools());
+        assert_eq!(sheet, "{{nmae}} is Ash\nOops {{name\n");
+    }
+
+    #[test]
+    fn palette_per_format() {
+        let mut character = character();
+        character.palette = Some(Palette {
+            harmony: Harmony::Complementary,
+            swatches: vec![Swatch {
+                role: SwatchRole::Main,
+                hex: "#FF8800".to_string(),
+            }],
+        });
+        let label = SwatchRole::Main.label();
+        let pools = pools();
+
+        assert_eq!(
+            render(&character, CommissionFormat::Markdown, "{{palette}}\n{{main}}", &pools),
+            format!("- **{label}:** `#FF8800`\n#FF8800\n")
+        );
+        assert_eq!(
+            render(&character, CommissionFormat::PlainText, "{{palette}}", &pools),
+            format!("  {label}: #FF8800\n")
+        );
+    }
+}
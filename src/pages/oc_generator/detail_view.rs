@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The character sheet of a saved OC
+//!
+//! Every sheet field with the character's tags, role, collections and relationships, and an
+//! edit form over the same fields while editing.
+
+use super::card::CardFormat;
+use super::character::SheetField;
+use super::commission::CommissionFormat;
+use super::{Message, OcGeneratorPage, SavedOC};
+use crate::fl;
+use cosmic::iced::alignment::{Horizontal, Vertical};
+use cosmic::iced::Length;
+use cosmic::widget::{self, column, icon, row, text};
+use cosmic::{cosmic_theme, prelude::*, theme};
+
+impl OcGeneratorPage {
+    /// Detail view for a saved character, or its edit form while editing
+    pub(super) fn character_detail<'a>(&'a self, saved: &'a SavedOC) -> Element<'a, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_xs, space_s, space_m, ..
+        } = theme::active().cosmic().spacing;
+
+        // Links from relationships and the graph lead here too, so hide it like in the list
+        if self.is_hidden(saved) {
+            return column::column()
+                .spacing(space_m)
+                .push(self.hidden_character_card(saved.id))
+                .push(
+                    widget::button::standard(fl!("back-button"))
+                        .on_press(Message::CloseCharacter),
+                )
+                .padding([0, space_xxs])
+                .apply(widget::container)
+                .width(Length::Fill)
+                .height(Length::FillPortion(3))
+                .into();
+        }
+
+        let sheet = &saved.sheet;
+
+        let mut fields = column::column().spacing(space_xs);
+        for field in SheetField::ALL {
+            let value: Element<'_, Message> = match &self.sheet_draft {
+                Some(draft) => widget::text_input(
+                    field.label(),
+                    draft.get(&field).map(String::as_str).unwrap_or_default(),
+                )
+                .on_input(move |value| Message::SheetFieldChanged(field, value))
+                .width(Length::Fill)
+                .into(),
+                None => text::body(sheet.field(field)).width(Length::Fill).into(),
+            };
+
+            fields = fields.push(
+                row()
+                    .spacing(space_s)
+                    .align_y(Vertical::Center)
+                    .push(text::heading(field.label()).width(Length::Fixed(140.0)))
+                    .push(value),
+            );
+        }
+
+        let tags: Element<'_, Message> = if self.sheet_draft.is_some() {
+            widget::text_input(fl!("tags"), &self.tags_draft)
+                .on_input(Message::TagsChanged)
+                .width(Length::Fill)
+                .into()
+        } else {
+            text::body(saved.tags.join(", ")).width(Length::Fill).into()
+        };
+        fields = fields.push(
+            row()
+                .spacing(space_s)
+                .align_y(Vertical::Center)
+                .push(text::heading(fl!("tags")).width(Length::Fixed(140.0)))
+                .push(tags),
+        );
+
+        if let Some(role) = saved.role {
+            fields = fields.push(
+                row()
+                    .spacing(space_s)
+                    .align_y(Vertical::Center)
+                    .push(text::heading(fl!("party-role")).width(Length::Fixed(140.0)))
+                    .push(text::body(role.label()).width(Length::Fill)),
+            );
+        }
+
+        if !self.collections.is_empty() {
+            let mut memberships = row().spacing(space_s);
+            for collection in &self.collections {
+                let name = collection.clone();
+                memberships = memberships.push(
+                    widget::checkbox(collection.clone(), saved.collections.contains(collection))
+                        .on_toggle(move |member| {
+                            Message::CollectionMembershipToggled(name.clone(), member)
+                        }),
+                );
+            }
+            fields = fields.push(
+                row()
+                    .spacing(space_s)
+                    .align_y(Vertical::Center)
+                    .push(text::heading(fl!("collections")).width(Length::Fixed(140.0)))
+                    .push(memberships),
+            );
+        }
+
+        if self.sheet_draft.is_none() {
+            fields = fields.push(self.relationship_editor(saved.id));
+        }
+
+        let buttons = if self.sheet_draft.is_some() {
+            row()
+                .spacing(space_m)
+                .push(text::caption(fl!("sheet-list-hint")))
+                .push(widget::horizontal_space())
+                .push(
+                    widget::button::standard(fl!("cancel-button"))
+                        .on_press(Message::CancelCharacterEdits),
+                )
+                .push(
+                    widget::button::standard(fl!("save-changes-button"))
+                        .on_press(Message::SaveCharacterEdits)
+                        .class(theme::Button::Suggested),
+                )
+        } else {
+            row()
+                .spacing(space_m)
+                .push(
+                    widget::button::standard(fl!("back-button"))
+                        .on_press(Message::CloseCharacter),
+                )
+                .push(widget::horizontal_space())
+                .push(
+                    widget::button::standard(fl!("export-card-svg-button"))
+                        .on_press(Message::ExportCard(saved.id, CardFormat::Svg)),
+                )
+                .push(
+                    widget::button::standard(fl!("export-card-png-button"))
+                        .on_press(Message::ExportCard(saved.id, CardFormat::Png)),
+                )
+                .push(
+                    widget::button::standard(CommissionFormat::Markdown.label())
+                        .on_press(Message::ExportCommission(saved.id, CommissionFormat::Markdown)),
+                )
+                .push(
+                    widget::button::standard(CommissionFormat::PlainText.label())
+                        .on_press(Message::ExportCommission(saved.id, CommissionFormat::PlainText)),
+                )
+                .push(
+                    widget::button::icon(icon::from_name("folder-open-symbolic"))
+                        .tooltip(fl!("edit-templates-button"))
+                        .on_press(Message::EditCommissionTemplates),
+                )
+                .push(
+                    widget::button::standard(fl!("edit-button"))
+                        .on_press(Message::EditCharacter),
+                )
+        };
+
+        column::column()
+            .spacing(space_s)
+            .push(
+                text::title3(sheet.title())
+                    .apply(widget::container)
+                    .width(Length::Fill)
+                    .align_x(Horizontal::Center),
+            )
+            .push_maybe(saved.palette.as_ref().map(|palette| self.palette_row(palette, &sheet.name)))
+            .push(widget::scrollable(fields).height(Length::Fill))
+            .push(buttons)
+            .padding([0, space_xxs])
+            .apply(widget::container)
+            .width(Length::Fill)
+            .height(Length::FillPortion(3))
+            .into()
+    }
+}
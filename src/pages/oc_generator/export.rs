@@ -0,0 +1,293 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Getting saved OCs in and out of the app
+//!
+//! JSON is the `SavedOC` serde format, so it round-trips through import. Markdown is meant for
+//! reading and pasting into docs, CSV for spreadsheets.
+
+use super::character::SheetField;
+use super::SavedOC;
+use crate::fl;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Json,
+    Markdown,
+    Csv,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::Json, ExportFormat::Markdown, ExportFormat::Csv];
+
+    pub fn label(self) -> String {
+        match self {
+            ExportFormat::Json => fl!("format-json"),
+            ExportFormat::Markdown => fl!("format-markdown"),
+            ExportFormat::Csv => fl!("format-csv"),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Json => "json",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Csv => "csv",
+        }
+    }
+}
+
+/// Write characters out in the given format
+pub fn export(characters: &[&SavedOC], format: ExportFormat) -> Result<String, String> {
+    match format {
+        ExportFormat::Json => serde_json::to_string_pretty(characters).map_err(|e| e.to_string()),
+        ExportFormat::Markdown => Ok(to_markdown(characters)),
+        ExportFormat::Csv => to_csv(characters),
+    }
+}
+
+/// Read characters back from a JSON export
+///
+/// Files with colors that aren't `#RRGGBB` are turned away, those go straight into the SVG
+/// of cards and avatars.
+pub fn import_json(source: &str) -> Result<Vec<SavedOC>, String> {
+    let characters: Vec<SavedOC> = serde_json::from_str(source).map_err(|e| e.to_string())?;
+
+    let invalid = characters
+        .iter()
+        .filter_map(|character| character.palette.as_ref())
+        .flat_map(|palette| &palette.swatches)
+        .find(|swatch| !swatch.is_valid());
+    if let Some(swatch) = invalid {
+        return Err(fl!("import-invalid-color", color = swatch.hex.clone()));
+    }
+    Ok(characters)
+}
+
+/// Whether an imported character is one of the existing ones, even if it was edited since
+pub fn is_duplicate<'a>(imported: &SavedOC, mut existing: impl Iterator<Item = &'a SavedOC>) -> bool {
+    existing.any(|saved| is_same_character(imported, saved))
+}
+
+/// Saves from before ids can only be told apart by their sheet
+fn is_same_character(imported: &SavedOC, saved: &SavedOC) -> bool {
+    saved.created_at == imported.created_at
+        && if imported.id == 0 {
+            saved.sheet == imported.sheet
+        } else {
+            saved.id == imported.id
+        }
+}
+
+fn to_markdown(characters: &[&SavedOC]) -> String {
+    let mut markdown = String::new();
+
+    for character in characters {
+        let sheet = &character.sheet;
+        markdown.push_str(&format!("# {}\n\n", sheet.title()));
+
+        // Short fields as a list, the long ones get their own paragraphs below
+        for field in SheetField::ALL {
+            let value = sheet.field(field);
+            let is_paragraph = matches!(field, SheetField::Description | SheetField::Notes);
+            if field != SheetField::Name && !is_paragraph && !value.is_empty() {
+                markdown.push_str(&format!("- **{}:** {value}\n", field.label()));
+            }
+        }
+        if !character.tags.is_empty() {
+            markdown.push_str(&format!("- **{}:** {}\n", fl!("tags"), character.tags.join(", ")));
+        }
+        if let Some(palette) = &character.palette {
+            let colors: Vec<String> = palette
+                .swatches
+                .iter()
+                .map(|swatch| format!("{} `{}`", swatch.role.label(), swatch.hex))
+                .collect();
+            markdown.push_str(&format!("- **{}:** {}\n", fl!("palette"), colors.join(", ")));
+        }
+
+        for text in [&sheet.description, &sheet.notes] {
+            if !text.is_empty() {
+                markdown.push_str(&format!("\n{text}\n"));
+            }
+        }
+        markdown.push('\n');
+    }
+
+    markdown
+}
+
+fn to_csv(characters: &[&SavedOC]) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+
+    // Headers stay in English so spreadsheets and scripts can rely on them
+    writer
+        .write_record([
+            "id",
+            "created_at",
+            "name",
+            "pronouns",
+            "age",
+            "species",
+            "parent_species",
+            "attributes",
+            "quirks",
+            "backstory",
+            "description",
+            "notes",
+            "tags",
+            "collections",
+            "palette",
+        ])
+        .map_err(|e| e.to_string())?;
+
+    for character in characters {
+        let sheet = &character.sheet;
+        let palette = character
+            .palette
+            .iter()
+            .flat_map(|palette| palette.swatches.iter().map(|swatch| swatch.hex.as_str()))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        writer
+            .write_record([
+                character.id.to_string(),
+                character.created_at.to_string(),
+                sheet.name.clone(),
+                sheet.pronouns.clone(),
+                sheet.age.clone(),
+                sheet.species.clone(),
+                sheet.parent_species.join("; "),
+                sheet.attributes.join("; "),
+                sheet.quirks.join("; "),
+                sheet.backstory.clone(),
+                sheet.description.clone(),
+                sheet.notes.clone(),
+                character.tags.join("; "),
+                character.collections.join("; "),
+                palette,
+            ])
+            .map_err(|e| e.to_string())?;
+    }
+
+    let bytes = writer.into_inner().map_err(|e| e.to_string())?;
+    String::from_utf8(bytes).map_err(|e| e.to_string())
+}
+
+/// What an import did, shown until the user dismisses it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportSummary {
+    /// Titles of the characters that were added
+    pub added: Vec<String>,
+    /// Titles of the characters that were already saved
+    pub skipped: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::oc_generator::character::CharacterSheet;
+    use crate::pages::oc_generator::palette::{Harmony, Palette, Swatch, SwatchRole};
+
+    fn character(id: u64, name: &str) -> SavedOC {
+        let sheet = CharacterSheet {
+            name: name.to_string(),
+            species: "fox".to_string(),
+            attributes: vec!["tall".to_string(), "shy".to_string()],
+            description: "A tall, shy fox.".to_string(),
+            ..Default::default()
+        };
+        let mut character = SavedOC::new(id, sheet);
+        character.created_at = 1_700_000_000_000;
+        character.tags = vec!["fursona".to_string()];
+        character.palette = Some(Palette {
+            harmony: Harmony::Complementary,
+            swatches: vec![Swatch {
+                role: SwatchRole::Main,
+                hex: "#FF8800".to_string(),
+            }],
+        });
+        character
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let ash = character(7, "Ash");
+        let json = export(&[&ash], ExportFormat::Json).unwrap();
+        let imported = import_json(&json).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].id, 7);
+        assert_eq!(imported[0].sheet, ash.sheet);
+        assert_eq!(imported[0].palette, ash.palette);
+    }
+
+    #[test]
+    fn import_rejects_bad_colors() {
+        let mut ash = character(7, "Ash");
+        ash.palette.as_mut().unwrap().swatches[0].hex = r#"red"/><script/>"#.to_string();
+        let json = export(&[&ash], ExportFormat::Json).unwrap();
+        assert!(import_json(&json).unwrap_err().contains("red"));
+        assert!(import_json("not json").is_err());
+    }
+
+    #[test]
+    fn duplicates_match_on_id_and_creation_time() {
+        let saved = character(7, "Ash");
+
+        // Edited since, still the same character
+        let mut edited = character(7, "Ashley");
+        assert!(is_same_character(&edited, &saved));
+
+        // Same id but made at another time is someone else
+        edited.created_at += 1;
+        assert!(!is_same_character(&edited, &saved));
+        assert!(!is_same_character(&character(8, "Ash"), &saved));
+
+        // Saves from before ids go by their sheet
+        let mut legacy_saved = character(3, "Ash");
+        let legacy = character(0, "Ash");
+        assert!(is_same_character(&legacy, &legacy_saved));
+        legacy_saved.sheet.name = "Ashley".to_string();
+        assert!(!is_same_character(&legacy, &legacy_saved));
+    }
+
+    #[test]
+    fn duplicates_in_the_trash_count() {
+        let saved = [character(1, "Bo")];
+        let trashed = [character(7, "Ash")];
+        let existing = || saved.iter().chain(&trashed);
+
+        assert!(is_duplicate(&character(7, "Ash"), existing()));
+        assert!(is_duplicate(&character(1, "Bo"), existing()));
+        assert!(!is_duplicate(&character(9, "Cy"), existing()));
+    }
+
+    #[test]
+    fn markdown() {
+        let ash = character(7, "Ash");
+        let markdown = export(&[&ash], ExportFormat::Markdown).unwrap();
+        assert!(markdown.starts_with("# Ash\n\n"));
+        assert!(markdown.contains(&format!("- **{}:** fox\n", SheetField::Species.label())));
+        assert!(markdown.contains(&format!("- **{}:** tall, shy\n", SheetField::Attributes.label())));
+        assert!(markdown.contains(&format!("- **{}:** fursona\n", fl!("tags"))));
+        assert!(markdown.contains("`#FF8800`"));
+        assert!(markdown.contains("\nA tall, shy fox.\n"));
+        // No empty fields
+        assert!(!markdown.contains(&SheetField::Age.label()));
+    }
+
+    #[test]
+    fn csv() {
+        let mut ash = character(7, "Ash");
+        ash.sheet.notes = "Likes \"quotes\", and commas".to_string();
+        let csv = export(&[&ash, &character(8, "Bo")], ExportFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("id,created_at,name,"));
+        assert!(lines[1].starts_with("7,1700000000000,Ash,,,fox,,tall; shy,"));
+        assert!(lines[1].contains(r#""Likes ""quotes"", and commas""#));
+        assert!(lines[1].ends_with(",fursona,,#FF8800"));
+        assert!(lines[2].starts_with("8,"));
+    }
+}
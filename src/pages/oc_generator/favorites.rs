@@ -0,0 +1,172 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Searching and sorting saved OCs
+//!
+//! Favorites lists get long, so the page filters them down with a search query and a few chips
+//! before showing them. Matching is forgiving: a plain substring always matches, and so does
+//! a query whose letters show up in order ("sprkl" finds "sparkle").
+//!
+//! Pinned characters always come first. "My order" is the order of the saved characters
+//! themselves, which the move up and down buttons rearrange.
+
+use super::SavedOC;
+use crate::fl;
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SortOrder {
+    #[default]
+    Newest,
+    Oldest,
+    Alphabetical,
+    /// The order the user put them in
+    Manual,
+}
+
+impl SortOrder {
+    pub const ALL: [SortOrder; 4] = [
+        SortOrder::Newest,
+        SortOrder::Oldest,
+        SortOrder::Alphabetical,
+        SortOrder::Manual,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            SortOrder::Newest => fl!("sort-newest"),
+            SortOrder::Oldest => fl!("sort-oldest"),
+            SortOrder::Alphabetical => fl!("sort-alphabetical"),
+            SortOrder::Manual => fl!("sort-manual"),
+        }
+    }
+
+    pub fn compare(self, a: &SavedOC, b: &SavedOC) -> Ordering {
+        match self {
+            SortOrder::Newest => b.created_at.cmp(&a.created_at),
+            SortOrder::Oldest => a.created_at.cmp(&b.created_at),
+            SortOrder::Alphabetical => a
+                .sheet
+                .title()
+                .to_lowercase()
+                .cmp(&b.sheet.title().to_lowercase()),
+            // Sorting is stable, so this keeps the saved order
+            SortOrder::Manual => Ordering::Equal,
+        }
+    }
+}
+
+/// Whether a saved OC matches the search query, an empty query matches everything
+///
+/// Any text on the sheet can match as a substring. Fuzzy matches only count against short
+/// fields like the name, species, attributes, quirks and tags, long descriptions would match
+/// almost any handful of letters.
+pub fn matches(query: &str, character: &SavedOC) -> bool {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return true;
+    }
+
+    let sheet = &character.sheet;
+    let short_fields = || {
+        [sheet.name.as_str(), sheet.species.as_str()]
+            .into_iter()
+            .chain(sheet.parent_species.iter().map(String::as_str))
+            .chain(sheet.attributes.iter().map(String::as_str))
+            .chain(sheet.quirks.iter().map(String::as_str))
+            .chain(character.tags.iter().map(String::as_str))
+    };
+    let long_fields = [
+        sheet.description.as_str(),
+        sheet.backstory.as_str(),
+        sheet.notes.as_str(),
+    ];
+
+    short_fields()
+        .chain(long_fields)
+        .any(|field| field.to_lowercase().contains(&query))
+        || short_fields().any(|field| is_subsequence(&query, &field.to_lowercase()))
+}
+
+/// Every character of the query shows up in the text, in order
+fn is_subsequence(query: &str, text: &str) -> bool {
+    let mut text = text.chars();
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .all(|c| text.any(|t| t == c))
+}
+
+/// When a character was saved, in the user's timezone
+pub fn created_date(character: &SavedOC) -> String {
+    format_date(character.created_at)
+}
+
+/// A millisecond timestamp as a date in the user's timezone
+pub fn format_date(timestamp_millis: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp_millis as i64)
+        .map(|date| date.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::oc_generator::character::CharacterSheet;
+
+    fn character(name: &str, created_at: u64) -> SavedOC {
+        let sheet = CharacterSheet {
+            name: name.to_string(),
+            species: "fox".to_string(),
+            attributes: vec!["sparkly".to_string()],
+            description: "Collects shiny pebbles from the riverbank.".to_string(),
+            ..Default::default()
+        };
+        let mut character = SavedOC::new(0, sheet);
+        character.created_at = created_at;
+        character.tags = vec!["fursona".to_string()];
+        character
+    }
+
+    #[test]
+    fn empty_query_matches_everything() {
+        assert!(matches("", &character("Ash", 0)));
+        assert!(matches("   ", &character("Ash", 0)));
+    }
+
+    #[test]
+    fn substrings_match_any_field() {
+        let ash = character("Ash", 0);
+        assert!(matches("ASH", &ash));
+        assert!(matches("fursona", &ash));
+        assert!(matches("shiny pebbles", &ash));
+        assert!(!matches("wolf", &ash));
+    }
+
+    #[test]
+    fn fuzzy_matches_only_count_on_short_fields() {
+        let ash = character("Ash", 0);
+        assert!(matches("sprkl", &ash));
+        assert!(matches("frsna", &ash));
+        // "crvbnk" is in order in the description, which is too long to match fuzzily
+        assert!(!matches("crvbnk", &ash));
+        // Letters out of order don't count
+        assert!(!matches("lkrps", &ash));
+    }
+
+    #[test]
+    fn sort_orders() {
+        let mut characters = vec![character("bo", 2), character("Ash", 3), character("Cy", 1)];
+        let names = |characters: &[SavedOC]| -> Vec<String> {
+            characters.iter().map(|c| c.sheet.name.clone()).collect()
+        };
+
+        characters.sort_by(|a, b| SortOrder::Newest.compare(a, b));
+        assert_eq!(names(&characters), ["Ash", "bo", "Cy"]);
+        characters.sort_by(|a, b| SortOrder::Oldest.compare(a, b));
+        assert_eq!(names(&characters), ["Cy", "bo", "Ash"]);
+        characters.sort_by(|a, b| SortOrder::Alphabetical.compare(a, b));
+        assert_eq!(names(&characters), ["Ash", "bo", "Cy"]);
+        characters.sort_by(|a, b| SortOrder::Manual.compare(a, b));
+        assert_eq!(names(&characters), ["Ash", "bo", "Cy"]);
+    }
+}
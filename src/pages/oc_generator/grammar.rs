@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! A tiny Tracery-style grammar engine
+//!
+//! The OC sentence itself comes from fluent, this fills in the `#rule#`s packs bring on top.
+//! Templates are plain text with `#rule#` placeholders. Each rule has a list of alternatives, one
+//! of which is picked at random and expanded in turn, so rules can nest. Modifiers chained after
+//! the rule name tweak the result, e.g. `#attribute.a.capitalize#` turns "energetic" into
+//! "An energetic".
+//!
+//! Supported modifiers: `a` (a/an article), `s` (plural), `capitalize` and `capitalizeAll`.
+//! Unknown rules are left in the output as `((rule))` so typos are easy to spot.
+
+use std::collections::{BTreeMap, HashMap};
+
+/// How deep rules may nest before we give up, so a rule that references itself can't hang the app
+const MAX_DEPTH: usize = 16;
+/// How many rules one template may expand in total, since a rule using itself several times
+/// grows exponentially well within `MAX_DEPTH`
+const MAX_EXPANSIONS: usize = 1000;
+
+#[derive(Debug, Clone, Default)]
+pub struct Grammar {
+    rules: HashMap<String, Vec<String>>,
+}
+
+impl Grammar {
+    /// Replace a rule's alternatives
+    pub fn set_rule(&mut self, name: impl Into<String>, alternatives: Vec<String>) {
+        self.rules.insert(name.into(), alternatives);
+    }
+
+    /// Add alternatives from a pack, appending to rules that already exist
+    pub fn add_rules(&mut self, rules: &BTreeMap<String, Vec<String>>) {
+        for (name, alternatives) in rules {
+            self.rules
+                .entry(name.clone())
+                .or_default()
+                .extend(alternatives.iter().cloned());
+        }
+    }
+
+    /// Expand every `#rule#` in a template
+    ///
+    /// Rules past `MAX_DEPTH` or `MAX_EXPANSIONS` are left as `((rule))` like unknown ones.
+    pub fn expand(&self, template: &str, rng: &mut fastrand::Rng) -> String {
+        let mut budget = MAX_EXPANSIONS;
+        self.expand_at_depth(template, rng, 0, &mut budget)
+    }
+
+    fn expand_at_depth(
+        &self,
+        template: &str,
+        rng: &mut fastrand::Rng,
+        depth: usize,
+        budget: &mut usize,
+    ) -> String {
+        let mut output = String::with_capacity(template.len());
+        let mut chars = template.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                // \# for a literal hash
+                '\\' => output.extend(chars.next()),
+                '#' => {
+                    let tag: String = chars.by_ref().take_while(|&c| c != '#').collect();
+                    output.push_str(&self.expand_tag(&tag, rng, depth, budget));
+                }
+                c => output.push(c),
+            }
+        }
+
+        output
+    }
+
+    /// Expand a single `rule.modifier.modifier` tag
+    fn expand_tag(&self, tag: &str, rng: &mut fastrand::Rng, depth: usize, budget: &mut usize) -> String {
+        let mut parts = tag.split('.');
+        let name = parts.next().unwrap_or_default();
+
+        let alternatives = match self.rules.get(name) {
+            Some(alternatives) if !alternatives.is_empty() && depth < MAX_DEPTH && *budget > 0 => {
+                alternatives
+            }
+            _ => return format!("(({name}))"),
+        };
+        *budget -= 1;
+
+        let picked = &alternatives[rng.usize(0..alternatives.len())];
+        let expanded = self.expand_at_depth(picked, rng, depth + 1, budget);
+
+        parts.fold(expanded, |text, modifier| apply_modifier(modifier, &text))
+    }
+}
+
+fn apply_modifier(modifier: &str, text: &str) -> String {
+    match modifier {
+        "a" => format!("{} {text}", article(text)),
+        "s" => plural(text),
+        "capitalize" => capitalize(text),
+        "capitalizeAll" => text.split(' ').map(capitalize).collect::<Vec<_>>().join(" "),
+        _ => text.to_string(),
+    }
+}
+
+/// "a" or "an" depending on how the word (probably) sounds
+pub fn article(text: &str) -> &'static str {
+    let word = text.trim_start().to_lowercase();
+
+    // English is weird, these are the usual suspects
+    let sounds_like_consonant = ["uni", "use", "usu", "eu", "one", "once"];
+    let sounds_like_vowel = ["hour", "honest", "honor", "heir"];
+
+    if sounds_like_vowel.iter().any(|prefix| word.starts_with(prefix)) {
+        "an"
+    } else if sounds_like_consonant.iter().any(|prefix| word.starts_with(prefix)) {
+        "a"
+    } else if word.starts_with(['a', 'e', 'i', 'o', 'u']) {
+        "an"
+    } else {
+        "a"
+    }
+}
+
+fn plural(text: &str) -> String {
+    let lower = text.to_lowercase();
+
+    if ["s", "x", "z", "ch", "sh"].iter().any(|end| lower.ends_with(end)) {
+        format!("{text}es")
+    } else if let Some(stem) = text
+        .strip_suffix('y')
+        .filter(|stem| !stem.ends_with(['a', 'e', 'i', 'o', 'u']))
+    {
+        format!("{stem}ies")
+    } else {
+        format!("{text}s")
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(grammar: &Grammar, template: &str) -> String {
+        grammar.expand(template, &mut fastrand::Rng::with_seed(1))
+    }
+
+    #[test]
+    fn expands_nested_rules_and_modifiers() {
+        let mut grammar = Grammar::default();
+        grammar.set_rule("attribute", vec!["energetic".into()]);
+        grammar.set_rule("oc", vec!["#attribute.a.capitalize# #animal#".into()]);
+        grammar.set_rule("animal", vec!["fox".into()]);
+
+        assert_eq!(expand(&grammar, "#oc#!"), "An energetic fox!");
+        assert_eq!(expand(&grammar, "two #animal.s#"), "two foxes");
+        assert_eq!(expand(&grammar, "#animal.capitalizeAll# \\#1"), "Fox #1");
+    }
+
+    #[test]
+    fn add_rules_appends() {
+        let mut grammar = Grammar::default();
+        grammar.set_rule("animal", vec!["fox".into()]);
+        grammar.add_rules(&BTreeMap::from([("animal".to_string(), vec!["fox".to_string()])]));
+        grammar.add_rules(&BTreeMap::from([("color".to_string(), vec!["teal".to_string()])]));
+
+        assert_eq!(grammar.rules["animal"].len(), 2);
+        assert_eq!(expand(&grammar, "#color#"), "teal");
+    }
+
+    #[test]
+    fn unknown_and_runaway_rules_are_marked() {
+        let mut grammar = Grammar::default();
+        assert_eq!(expand(&grammar, "#nope#"), "((nope))");
+
+        grammar.set_rule("loop", vec!["#loop#".into()]);
+        assert_eq!(expand(&grammar, "#loop#"), "((loop))");
+
+        // Doubles with every level, so only the expansion budget stops it
+        grammar.set_rule("grow", vec!["#grow##grow#".into()]);
+        let expanded = expand(&grammar, "#grow#");
+        assert!(expanded.contains("((grow))"));
+        assert!(expanded.len() < 100_000);
+    }
+
+    #[test]
+    fn articles() {
+        assert_eq!(article("energetic"), "an");
+        assert_eq!(article("tall"), "a");
+        assert_eq!(article("unicorn"), "a");
+        assert_eq!(article("honest"), "an");
+        assert_eq!(article("  Orange"), "an");
+    }
+
+    #[test]
+    fn plurals_and_capitals() {
+        assert_eq!(plural("fox"), "foxes");
+        assert_eq!(plural("bunny"), "bunnies");
+        assert_eq!(plural("monkey"), "monkeys");
+        assert_eq!(plural("cat"), "cats");
+        assert_eq!(capitalize("élan"), "Élan");
+        assert_eq!(capitalize(""), "");
+    }
+}
@@ -0,0 +1,133 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The relationship graph, drawn on a canvas
+//!
+//! Characters sit on a circle in the order they were saved, each dot filled with the
+//! character's main color. Relationships are lines between them, colored by kind. Two
+//! characters with several relationships get parallel lines so none of them hide the others.
+
+use cosmic::iced::mouse;
+use cosmic::iced::widget::canvas::{self, Frame, Geometry, Path, Stroke};
+use cosmic::iced::{Color, Point, Rectangle, Vector};
+
+const NODE_RADIUS: f32 = 10.0;
+/// Room around the circle for names
+const MARGIN: f32 = 70.0;
+/// Distance between parallel lines for the same two characters
+const LINE_GAP: f32 = 6.0;
+
+pub struct Node {
+    pub label: String,
+    pub color: Color,
+}
+
+pub struct Edge {
+    /// Indices into `RelationshipGraph::nodes`
+    pub between: [usize; 2],
+    pub label: String,
+    pub color: Color,
+}
+
+#[derive(Default)]
+pub struct RelationshipGraph {
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+impl RelationshipGraph {
+    /// Where each node goes for a canvas of the given bounds
+    fn positions(&self, bounds: Rectangle) -> Vec<Point> {
+        let center = Point::new(bounds.width / 2.0, bounds.height / 2.0);
+        if self.nodes.len() == 1 {
+            return vec![center];
+        }
+
+        let radius = (bounds.width.min(bounds.height) / 2.0 - MARGIN).max(NODE_RADIUS * 2.0);
+        (0..self.nodes.len())
+            .map(|index| {
+                // Start at the top and go clockwise
+                let angle = index as f32 / self.nodes.len() as f32 * std::f32::consts::TAU
+                    - std::f32::consts::FRAC_PI_2;
+                Point::new(
+                    center.x + radius * angle.cos(),
+                    center.y + radius * angle.sin(),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<Message> canvas::Program<Message, cosmic::Theme, cosmic::Renderer> for RelationshipGraph {
+    type State = ();
+
+    fn draw(
+        &self,
+        _state: &Self::State,
+        renderer: &cosmic::Renderer,
+        theme: &cosmic::Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<Geometry> {
+        let mut frame = Frame::new(renderer, bounds.size());
+        let text_color: Color = theme.cosmic().on_bg_color().into();
+        let positions = self.positions(bounds);
+
+        for (index, edge) in self.edges.iter().enumerate() {
+            let [from, to] = edge.between.map(|node| positions[node]);
+
+            // Spread out lines between the same pair: 0, +1, -1, +2...
+            let earlier = self.edges[..index]
+                .iter()
+                .filter(|other| {
+                    other.between == edge.between
+                        || other.between == [edge.between[1], edge.between[0]]
+                })
+                .count();
+            let step = (earlier as f32 / 2.0).ceil() * if earlier % 2 == 0 { -1.0 } else { 1.0 };
+            let direction = Vector::new(to.x - from.x, to.y - from.y);
+            let length = (direction.x * direction.x + direction.y * direction.y)
+                .sqrt()
+                .max(1.0);
+            let offset = Vector::new(
+                -direction.y / length * step * LINE_GAP,
+                direction.x / length * step * LINE_GAP,
+            );
+            let (from, to) = (from + offset, to + offset);
+
+            frame.stroke(
+                &Path::line(from, to),
+                Stroke::default().with_color(edge.color).with_width(2.0),
+            );
+            frame.fill_text(canvas::Text {
+                content: edge.label.clone(),
+                position: Point::new((from.x + to.x) / 2.0 + 4.0, (from.y + to.y) / 2.0 - 16.0),
+                color: edge.color,
+                size: 12.0.into(),
+                ..canvas::Text::default()
+            });
+        }
+
+        for (node, position) in self.nodes.iter().zip(&positions) {
+            frame.fill(&Path::circle(*position, NODE_RADIUS), node.color);
+            frame.stroke(
+                &Path::circle(*position, NODE_RADIUS),
+                Stroke::default().with_color(text_color).with_width(1.0),
+            );
+            frame.fill_text(canvas::Text {
+                content: node.label.clone(),
+                position: Point::new(position.x + NODE_RADIUS + 4.0, position.y - 8.0),
+                color: text_color,
+                size: 14.0.into(),
+                ..canvas::Text::default()
+            });
+        }
+
+        vec![frame.into_geometry()]
+    }
+}
+
+/// A `#RRGGBB` color for the canvas
+pub fn hex_color(hex: &str) -> Color {
+    let channel = |range| u8::from_str_radix(hex.get(range).unwrap_or("80"), 16).unwrap_or(128);
+    Color::from_rgb8(channel(1..3), channel(3..5), channel(5..7))
+}
@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Generation history
+//!
+//! Every OC shown on the page goes into a small ring buffer so a good roll isn't lost to one
+//! click too many. New results always go on the end, stepping back and generating again
+//! keeps the later results around instead of throwing them away like a browser would.
+
+use super::character::GeneratedOC;
+use std::collections::VecDeque;
+
+/// How many OCs the history keeps before dropping the oldest
+pub const CAPACITY: usize = 20;
+
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    entries: VecDeque<GeneratedOC>,
+    /// Index of the OC currently shown
+    position: usize,
+}
+
+impl History {
+    /// A history picking up where an earlier session left off
+    pub fn from_entries(entries: Vec<GeneratedOC>) -> Self {
+        let mut entries: VecDeque<GeneratedOC> = entries.into();
+        while entries.len() > CAPACITY {
+            entries.pop_front();
+        }
+
+        Self {
+            position: entries.len().saturating_sub(1),
+            entries,
+        }
+    }
+
+    /// Add a new OC and make it the current one
+    pub fn push(&mut self, oc: GeneratedOC) {
+        if self.entries.len() == CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(oc);
+        self.position = self.entries.len() - 1;
+    }
+
+    /// Swap out the current OC, like after toggling a lock
+    pub fn replace_current(&mut self, oc: GeneratedOC) {
+        if let Some(current) = self.entries.get_mut(self.position) {
+            *current = oc;
+        }
+    }
+
+    pub fn current(&self) -> Option<&GeneratedOC> {
+        self.entries.get(self.position)
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.position > 0
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.position + 1 < self.entries.len()
+    }
+
+    pub fn back(&mut self) -> Option<&GeneratedOC> {
+        self.jump(self.position.checked_sub(1)?)
+    }
+
+    pub fn forward(&mut self) -> Option<&GeneratedOC> {
+        self.jump(self.position + 1)
+    }
+
+    pub fn jump(&mut self, index: usize) -> Option<&GeneratedOC> {
+        if index < self.entries.len() {
+            self.position = index;
+        }
+        self.entries.get(index)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &GeneratedOC> {
+        self.entries.iter()
+    }
+
+    pub fn to_vec(&self) -> Vec<GeneratedOC> {
+        self.entries.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::oc_generator::character::{OcTrait, TraitSlot};
+
+    /// An OC told apart from the others by its name seed
+    fn oc(seed: u64) -> GeneratedOC {
+        GeneratedOC {
+            traits: vec![OcTrait {
+                slot: TraitSlot::Name,
+                entry_id: String::new(),
+                text: seed.to_string(),
+                seed,
+                parents: vec![],
+                features: Default::default(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn seeds(history: &History) -> Vec<u64> {
+        history.iter().map(|oc| oc.traits[0].seed).collect()
+    }
+
+    #[test]
+    fn drops_the_oldest_when_full() {
+        let mut history = History::default();
+        for seed in 0..CAPACITY as u64 + 5 {
+            history.push(oc(seed));
+        }
+        assert_eq!(seeds(&history), (5..CAPACITY as u64 + 5).collect::<Vec<_>>());
+        assert_eq!(history.position(), CAPACITY - 1);
+        assert_eq!(history.current(), Some(&oc(CAPACITY as u64 + 4)));
+    }
+
+    #[test]
+    fn back_and_forward() {
+        let mut history = History::default();
+        assert_eq!(history.back(), None);
+        for seed in 0..3 {
+            history.push(oc(seed));
+        }
+        assert!(!history.can_go_forward());
+        assert_eq!(history.forward(), None);
+        assert_eq!(history.position(), 2);
+
+        assert_eq!(history.back(), Some(&oc(1)));
+        assert_eq!(history.back(), Some(&oc(0)));
+        assert!(!history.can_go_back());
+        assert_eq!(history.back(), None);
+        assert_eq!(history.position(), 0);
+        assert_eq!(history.forward(), Some(&oc(1)));
+        assert!(history.can_go_back() && history.can_go_forward());
+    }
+
+    #[test]
+    fn pushing_after_going_back_keeps_later_results() {
+        let mut history = History::default();
+        for seed in 0..3 {
+            history.push(oc(seed));
+        }
+        history.back();
+        history.back();
+        history.push(oc(3));
+        assert_eq!(seeds(&history), [0, 1, 2, 3]);
+        assert_eq!(history.current(), Some(&oc(3)));
+    }
+
+    #[test]
+    fn replacing_only_touches_the_current_oc() {
+        let mut history = History::default();
+        for seed in 0..3 {
+            history.push(oc(seed));
+        }
+        history.jump(1);
+        history.replace_current(oc(9));
+        assert_eq!(seeds(&history), [0, 9, 2]);
+        // Jumping past the end stays put
+        assert_eq!(history.jump(7), None);
+        assert_eq!(history.position(), 1);
+    }
+
+    #[test]
+    fn restored_history_is_trimmed_and_ends_on_the_newest() {
+        let history = History::from_entries((0..CAPACITY as u64 + 2).map(oc).collect());
+        assert_eq!(history.iter().count(), CAPACITY);
+        assert_eq!(history.current(), Some(&oc(CAPACITY as u64 + 1)));
+        assert!(History::from_entries(vec![]).current().is_none());
+    }
+}
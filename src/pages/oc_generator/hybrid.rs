@@ -0,0 +1,108 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Hybrid species
+//!
+//! With hybrids switched on, a species roll can mix in a second species. Packs can name
+//! specific pairs in their `hybrid` category (fox + wolf is a "folf"), per locale like any other
+//! entry. Pairs without a name get a portmanteau made up on the spot, or the locale's
+//! `species-hybrid` description when a portmanteau would come out too short or can't be built.
+
+use super::packs::{self, PackEntry};
+use crate::config::HybridNaming;
+use crate::fl;
+use crate::i18n::strip_isolation_marks;
+use std::collections::BTreeMap;
+
+/// Hybrid chances offered in the dropdown, in percent
+pub const CHANCES: [u32; 4] = [0, 10, 25, 50];
+
+/// Mixed into the species seed so the hybrid roll doesn't just echo the species roll
+pub const HYBRID_SALT: u64 = 0x4B1D_F01F;
+
+/// Portmanteaus shorter than this read like typos ("cat" + "dog" = "cog")
+const MIN_PORTMANTEAU_LEN: usize = 4;
+
+/// Stable id for a pair of species, like `fox+wolf`
+pub fn pair_id(first: &PackEntry, second: &PackEntry) -> String {
+    format!("{}+{}", first.id, second.id)
+}
+
+/// The display name for a hybrid of two species, with its grammatical features
+///
+/// A pack entry naming the pair always wins, in the order given and then the other way round.
+/// Made up portmanteaus take the features of the second species, which they end like, and
+/// descriptions take the features of the `species-hybrid` message.
+pub fn name(
+    naming: HybridNaming,
+    named_hybrids: &[&PackEntry],
+    first: &PackEntry,
+    second: &PackEntry,
+) -> (String, BTreeMap<String, String>) {
+    let named = |a: &PackEntry, b: &PackEntry| {
+        named_hybrids
+            .iter()
+            .find(|entry| entry.parents.as_slice() == [a.id.as_str(), b.id.as_str()])
+    };
+    if let Some(entry) = named(first, second).or_else(|| named(second, first)) {
+        return (entry.label(), entry.features());
+    }
+
+    let (first_label, second_label) = (first.label(), second.label());
+    let portmanteau = match naming {
+        HybridNaming::Portmanteau => portmanteau(&first_label, &second_label),
+        HybridNaming::Descriptive => None,
+    };
+    match portmanteau {
+        Some(name) => (name, second.features()),
+        None => (
+            strip_isolation_marks(&fl!(
+                "species-hybrid",
+                first = first_label,
+                second = second_label
+            )),
+            packs::message_features("species-hybrid"),
+        ),
+    }
+}
+
+/// The start of one name up to its first vowel, then the rest of the other from its first vowel
+///
+/// "fox" + "wolf" gives "folf", "lion" + "tiger" gives "liger". Only works for names written
+/// with latin vowels, anything else falls back to the description.
+fn portmanteau(first: &str, second: &str) -> Option<String> {
+    let is_vowel = |c: char| "aeiouy".contains(c.to_ascii_lowercase());
+
+    let onset_end = first.find(is_vowel)?;
+    let rest_start = second.find(is_vowel)?;
+    if onset_end == 0 && rest_start == 0 {
+        // Two names starting with vowels would just give the second one back
+        return None;
+    }
+
+    let name = format!("{}{}", &first[..onset_end], &second[rest_start..]);
+    (name.chars().count() >= MIN_PORTMANTEAU_LEN && name != first && name != second).then_some(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn portmanteaus() {
+        assert_eq!(portmanteau("fox", "wolf").as_deref(), Some("folf"));
+        assert_eq!(portmanteau("lion", "tiger").as_deref(), Some("liger"));
+        assert_eq!(portmanteau("Lynx", "bobcat").as_deref(), Some("Lobcat"));
+    }
+
+    #[test]
+    fn no_portmanteau_when_it_would_read_badly() {
+        // Too short
+        assert_eq!(portmanteau("cat", "dog"), None);
+        // Both start with vowels
+        assert_eq!(portmanteau("otter", "eel"), None);
+        // Same as one of the parents
+        assert_eq!(portmanteau("bobcat", "bobcat"), None);
+        // No latin vowels to split on
+        assert_eq!(portmanteau("狐", "wolf"), None);
+    }
+}
@@ -0,0 +1,217 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Made-up names
+//!
+//! Each name flavor is a small character-level Markov model trained on a bundled list of names
+//! that sound right for it, so dragons get names like "Vorthrax" and woodland critters names
+//! like "Brindel". Species pick their flavor in the pack (`name_flavor = "draconic"`).
+//!
+//! Names that come out as a name from the list, a real word or something rude get thrown away
+//! and rolled again. The word list and blocklist are bundled too, so a seed rolls the same name
+//! on every machine.
+
+use crate::fl;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::LazyLock;
+
+/// Flavors with a bundled name list, in the order the dropdown shows them
+pub const FLAVORS: [&str; 6] = ["common", "draconic", "feline", "canine", "woodland", "nocturnal"];
+
+/// For species without a flavor, or with one that has no name list
+pub const DEFAULT_FLAVOR: &str = "common";
+
+/// Name ideas per click, and per `cosmi-kit names` unless asked for more
+pub const SUGGESTIONS: usize = 12;
+
+/// Mixed into the name's seed so the made-up name doesn't echo the pool pick
+pub const NAME_SALT: u64 = 0x0A4E_5EED;
+
+/// Letters of context the models look at, more copies the lists, less sounds like noise
+const ORDER: usize = 2;
+const MIN_LEN: usize = 4;
+const MAX_LEN: usize = 10;
+/// Tries before settling for a name straight from the list
+const ATTEMPTS: usize = 50;
+
+const START: char = '^';
+const END: char = '$';
+
+static MODELS: LazyLock<BTreeMap<&'static str, Model>> = LazyLock::new(|| {
+    [
+        ("common", include_str!("../../../resources/names/common.txt")),
+        ("draconic", include_str!("../../../resources/names/draconic.txt")),
+        ("feline", include_str!("../../../resources/names/feline.txt")),
+        ("canine", include_str!("../../../resources/names/canine.txt")),
+        ("woodland", include_str!("../../../resources/names/woodland.txt")),
+        ("nocturnal", include_str!("../../../resources/names/nocturnal.txt")),
+    ]
+    .into_iter()
+    .map(|(flavor, list)| (flavor, Model::train(list)))
+    .collect()
+});
+
+static WORDS: LazyLock<HashSet<String>> =
+    LazyLock::new(|| lines(include_str!("../../../resources/names/words.txt")).collect());
+
+static BLOCKLIST: LazyLock<Vec<String>> =
+    LazyLock::new(|| lines(include_str!("../../../resources/names/blocklist.txt")).collect());
+
+/// Lowercased entries of a bundled list, without comments and blank lines
+fn lines(list: &str) -> impl Iterator<Item = String> + '_ {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_lowercase)
+}
+
+pub fn flavor_label(flavor: &str) -> String {
+    match flavor {
+        "draconic" => fl!("name-flavor-draconic"),
+        "feline" => fl!("name-flavor-feline"),
+        "canine" => fl!("name-flavor-canine"),
+        "woodland" => fl!("name-flavor-woodland"),
+        "nocturnal" => fl!("name-flavor-nocturnal"),
+        _ => fl!("name-flavor-common"),
+    }
+}
+
+/// A new name of the given flavor, falling back to the common flavor for unknown ones
+pub fn generate(flavor: &str, rng: &mut fastrand::Rng) -> String {
+    let model = MODELS
+        .get(flavor)
+        .or_else(|| MODELS.get(DEFAULT_FLAVOR))
+        .expect("the default name list is bundled");
+
+    for _ in 0..ATTEMPTS {
+        if let Some(name) = model.sample(rng).filter(|name| model.is_acceptable(name)) {
+            return capitalize(&name);
+        }
+    }
+    capitalize(&model.names[rng.usize(..model.names.len())])
+}
+
+fn capitalize(name: &str) -> String {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+struct Model {
+    /// What follows each run of `ORDER` letters, and how often, in a fixed order
+    transitions: BTreeMap<Vec<char>, Vec<(char, u32)>>,
+    /// The training names, made-up names shouldn't just repeat one
+    names: Vec<String>,
+}
+
+impl Model {
+    fn train(list: &str) -> Self {
+        let names: Vec<String> = lines(list).collect();
+        let mut counts: BTreeMap<Vec<char>, BTreeMap<char, u32>> = BTreeMap::new();
+
+        for name in &names {
+            let padded: Vec<char> = std::iter::repeat_n(START, ORDER)
+                .chain(name.chars())
+                .chain(std::iter::once(END))
+                .collect();
+            for window in padded.windows(ORDER + 1) {
+                *counts
+                    .entry(window[..ORDER].to_vec())
+                    .or_default()
+                    .entry(window[ORDER])
+                    .or_default() += 1;
+            }
+        }
+
+        Self {
+            transitions: counts
+                .into_iter()
+                .map(|(context, next)| (context, next.into_iter().collect()))
+                .collect(),
+            names,
+        }
+    }
+
+    /// Walk the chain from the start, `None` if it runs too long
+    fn sample(&self, rng: &mut fastrand::Rng) -> Option<String> {
+        let mut context = vec![START; ORDER];
+        let mut name = String::new();
+
+        while name.chars().count() <= MAX_LEN {
+            let next = self.transitions.get(&context)?;
+            let total: u32 = next.iter().map(|(_, count)| count).sum();
+            let mut roll = rng.u32(..total);
+            let &(letter, _) = next.iter().find(|(_, count)| {
+                if roll < *count {
+                    true
+                } else {
+                    roll -= count;
+                    false
+                }
+            })?;
+
+            if letter == END {
+                return Some(name);
+            }
+            name.push(letter);
+            context.remove(0);
+            context.push(letter);
+        }
+        None
+    }
+
+    fn is_acceptable(&self, name: &str) -> bool {
+        (MIN_LEN..=MAX_LEN).contains(&name.chars().count())
+            && !self.names.iter().any(|known| known == name)
+            && !WORDS.contains(name)
+            && !BLOCKLIST.iter().any(|bad| name.contains(bad.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_name() {
+        for flavor in FLAVORS {
+            let names = |seed| {
+                let mut rng = fastrand::Rng::with_seed(seed);
+                (0..SUGGESTIONS).map(|_| generate(flavor, &mut rng)).collect::<Vec<_>>()
+            };
+            assert_eq!(names(42), names(42));
+            assert_ne!(names(42), names(43));
+        }
+    }
+
+    #[test]
+    fn unknown_flavors_are_common() {
+        let mut rng = fastrand::Rng::with_seed(5);
+        let mut common_rng = fastrand::Rng::with_seed(5);
+        assert_eq!(generate("nonsense", &mut rng), generate(DEFAULT_FLAVOR, &mut common_rng));
+    }
+
+    #[test]
+    fn names_are_capitalized_and_new() {
+        let mut rng = fastrand::Rng::with_seed(9);
+        for _ in 0..100 {
+            let name = generate("draconic", &mut rng);
+            assert!(name.starts_with(char::is_uppercase), "{name}");
+            assert!(!WORDS.contains(&name.to_lowercase()), "{name}");
+        }
+    }
+
+    #[test]
+    fn training_skips_comments_and_list_names_are_rejected() {
+        let model = Model::train("# names\nAnna\n\n  bob  \n");
+        assert_eq!(model.names, ["anna", "bob"]);
+        assert!(!model.is_acceptable("anna"));
+        assert!(!model.is_acceptable("abc"));
+        assert!(model.is_acceptable("annob"));
+
+        // With no letters shared between the names, every walk spells one of them out
+        let mut rng = fastrand::Rng::with_seed(1);
+        assert_eq!(model.sample(&mut rng).map(|name| model.names.contains(&name)), Some(true));
+    }
+}
@@ -0,0 +1,455 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Generator packs
+//!
+//! The OC generator's word pools live in "packs" instead of being hardcoded arrays. A pack is a
+//! TOML or JSON file that declares categories (attribute, species, name, backstory...) full of
+//! entries. Each entry either points at a fluent message or brings its own localized text.
+//!
+//! The bundled default pack is always loaded first, then every pack found in
+//! `$XDG_DATA_HOME/com.github.kitfoxboy.cosmi-kit/packs/` in file name order. Packs that come
+//! later can override an entry by reusing its id in the same category.
+
+use super::grammar::Grammar;
+use super::palette::PaletteRange;
+use crate::config::{ContentRating, CONFIG_KEY};
+use crate::i18n::LANGUAGE_LOADER;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Category ids the generator knows how to use
+pub const ATTRIBUTE: &str = "attribute";
+pub const SPECIES: &str = "species";
+pub const CHARACTERISTIC: &str = "characteristic";
+pub const NAME: &str = "name";
+pub const PRONOUNS: &str = "pronouns";
+pub const AGE: &str = "age";
+pub const BACKSTORY: &str = "backstory";
+/// Names for specific pairs of species, see `hybrid.rs`
+pub const HYBRID: &str = "hybrid";
+
+const DEFAULT_PACK: &str = include_str!("../../../resources/packs/default.toml");
+const DEFAULT_PACK_PATH: &str = "<bundled>/default.toml";
+
+/// A pack of word pools as written on disk
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct GeneratorPack {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub categories: BTreeMap<String, Vec<PackEntry>>,
+    /// Extra grammar rules that OC templates can reference as `#rule#`
+    #[serde(default)]
+    pub grammar: BTreeMap<String, Vec<String>>,
+    /// Where the pack was loaded from, filled in after parsing
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+/// A single thing the generator can pick, like "fox" or "with a mohawk"
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct PackEntry {
+    pub id: String,
+    /// Message id in the app's fluent files
+    #[serde(default)]
+    pub fluent: Option<String>,
+    /// Text shipped by the pack itself, either plain or keyed by locale
+    #[serde(default)]
+    pub text: Option<PackText>,
+    /// Entries above the user's content rating are never picked
+    #[serde(default)]
+    pub rating: ContentRating,
+    /// Color range for the OC's main color, only used by species
+    #[serde(default)]
+    pub palette: Option<PaletteRange>,
+    /// The two species ids a hybrid entry names, only used by hybrids
+    #[serde(default)]
+    pub parents: Vec<String>,
+    /// Which bundled name list made-up names for this species learn from, only used by species
+    #[serde(default)]
+    pub name_flavor: Option<String>,
+    /// Head and ears of the avatar, like `feline` or `dragon`, only used by species
+    #[serde(default)]
+    pub silhouette: Option<String>,
+    /// What the avatar wears or carries, like `glasses` or `cape`, only used by characteristics
+    #[serde(default)]
+    pub accessory: Option<String>,
+    /// Describes a body rather than a personality, only used by attributes
+    #[serde(default)]
+    pub build: bool,
+    /// Grammatical features like `gender` for entries that bring their own `text`, either
+    /// plain or keyed by locale. Fluent entries use their message's attributes instead
+    #[serde(default)]
+    pub features: BTreeMap<String, PackText>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(untagged)]
+pub enum PackText {
+    Plain(String),
+    Localized(HashMap<String, String>),
+}
+
+impl PackEntry {
+    /// The display text for the current locale
+    pub fn label(&self) -> String {
+        if let Some(message_id) = &self.fluent {
+            return LANGUAGE_LOADER.get(message_id);
+        }
+
+        self.text
+            .as_ref()
+            .and_then(PackText::localized)
+            .unwrap_or_else(|| self.id.clone())
+    }
+
+    /// Grammatical features for the current locale, handed to the `oc-sentence` message
+    pub fn features(&self) -> BTreeMap<String, String> {
+        if let Some(message_id) = &self.fluent {
+            return message_features(message_id);
+        }
+
+        self.features
+            .iter()
+            .filter_map(|(name, text)| Some((name.clone(), text.localized()?)))
+            .collect()
+    }
+}
+
+impl PackText {
+    /// The text for the current locale
+    pub fn localized(&self) -> Option<String> {
+        match self {
+            PackText::Plain(text) => Some(text.clone()),
+            PackText::Localized(texts) => localized_text(texts),
+        }
+    }
+}
+
+/// The attributes of a fluent message, like `.gender = feminine`
+///
+/// Attribute names come from the best matching locale that has the message, since that's the
+/// locale whose `oc-sentence` will be asking for them.
+pub fn message_features(message_id: &str) -> BTreeMap<String, String> {
+    let names = LANGUAGE_LOADER
+        .with_fluent_message(message_id, |message| {
+            message
+                .attributes()
+                .map(|attribute| attribute.id().to_string())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let value = LANGUAGE_LOADER.get_attr(message_id, &name);
+            (name, value)
+        })
+        .collect()
+}
+
+/// Pick the best match for the active languages, falling back to english, then anything at all
+fn localized_text(texts: &HashMap<String, String>) -> Option<String> {
+    for language in LANGUAGE_LOADER.current_languages() {
+        if let Some(text) = texts.get(&language.to_string()) {
+            return Some(text.clone());
+        }
+        if let Some(text) = texts.get(language.language.as_str()) {
+            return Some(text.clone());
+        }
+    }
+
+    texts
+        .get("en")
+        .or_else(|| texts.values().next())
+        .cloned()
+}
+
+/// A pack that failed to load, with enough info for the user to go fix it
+#[derive(Debug, Clone)]
+pub struct PackError {
+    pub path: PathBuf,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "{}:{}: {}", self.path.display(), line, self.message),
+            None => write!(f, "{}: {}", self.path.display(), self.message),
+        }
+    }
+}
+
+/// The directory user packs are discovered from
+pub fn user_pack_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(CONFIG_KEY).join("packs"))
+}
+
+/// Load the bundled pack plus every user pack, collecting errors instead of bailing out
+pub fn load_packs() -> (Vec<GeneratorPack>, Vec<PackError>) {
+    let mut packs = Vec::new();
+    let mut errors = Vec::new();
+
+    let mut sources = vec![(PathBuf::from(DEFAULT_PACK_PATH), Ok(DEFAULT_PACK.to_string()))];
+    if let Some(dir) = user_pack_dir() {
+        sources.extend(
+            pack_files(&dir)
+                .into_iter()
+                .map(|path| {
+                    let source = std::fs::read_to_string(&path);
+                    (path, source)
+                }),
+        );
+    }
+
+    for (path, source) in sources {
+        let pack = source
+            .map_err(|e| PackError {
+                path: path.clone(),
+                line: None,
+                message: e.to_string(),
+            })
+            .and_then(|source| parse_pack(&path, &source));
+
+        match pack {
+            Ok(pack) => {
+                if let Some(existing) = packs.iter().find(|p: &&GeneratorPack| p.id == pack.id) {
+                    errors.push(PackError {
+                        path,
+                        line: None,
+                        message: format!(
+                            "pack id `{}` is already used by {}",
+                            pack.id,
+                            existing.path.display()
+                        ),
+                    });
+                } else {
+                    packs.push(pack);
+                }
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    (packs, errors)
+}
+
+/// All `.toml` and `.json` files in the pack dir, sorted so the load order is stable
+fn pack_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        // No pack dir just means no user packs
+        return vec![];
+    };
+
+    let mut files: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("toml") | Some("json")
+            )
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Parse a pack from TOML or JSON (picked by file extension) and validate its entries
+pub fn parse_pack(path: &Path, source: &str) -> Result<GeneratorPack, PackError> {
+    let is_json = path.extension().and_then(|ext| ext.to_str()) == Some("json");
+
+    let mut pack: GeneratorPack = if is_json {
+        serde_json::from_str(source).map_err(|e| PackError {
+            path: path.to_path_buf(),
+            line: Some(e.line()),
+            message: e.to_string(),
+        })?
+    } else {
+        toml::from_str(source).map_err(|e| PackError {
+            path: path.to_path_buf(),
+            line: e.span().map(|span| line_of_offset(source, span.start)),
+            message: e.message().to_string(),
+        })?
+    };
+    pack.path = path.to_path_buf();
+
+    for (category, entries) in &pack.categories {
+        for entry in entries {
+            let problem = match (&entry.fluent, &entry.text) {
+                (None, None) => Some("needs either `fluent` or `text`".to_string()),
+                (Some(message_id), _) if !LANGUAGE_LOADER.has(message_id) => {
+                    Some(format!("unknown fluent message `{message_id}`"))
+                }
+                _ if category == HYBRID && entry.parents.len() != 2 => {
+                    Some("needs exactly two `parents`".to_string())
+                }
+                _ => None,
+            };
+
+            if let Some(problem) = problem {
+                return Err(PackError {
+                    path: path.to_path_buf(),
+                    line: line_of_text(source, &format!("\"{}\"", entry.id)),
+                    message: format!("entry `{}` in category `{category}` {problem}", entry.id),
+                });
+            }
+        }
+    }
+
+    Ok(pack)
+}
+
+/// 1-based line number of a byte offset
+fn line_of_offset(source: &str, offset: usize) -> usize {
+    source[..offset.min(source.len())].lines().count().max(1)
+}
+
+/// 1-based line number of the first line containing some text, good enough to point at an entry
+fn line_of_text(source: &str, needle: &str) -> Option<usize> {
+    source
+        .lines()
+        .position(|line| line.contains(needle))
+        .map(|index| index + 1)
+}
+
+/// Word pools merged from all enabled packs
+#[derive(Debug, Clone, Default)]
+pub struct WordPools {
+    categories: HashMap<String, Vec<PackEntry>>,
+    grammar: Grammar,
+}
+
+impl WordPools {
+    /// Merge enabled packs in load order, later packs replacing entries with the same id
+    pub fn merge(packs: &[GeneratorPack], disabled: &[String]) -> Self {
+        let mut categories: HashMap<String, Vec<PackEntry>> = HashMap::new();
+        let mut grammar = Grammar::default();
+
+        for pack in packs.iter().filter(|pack| !disabled.contains(&pack.id)) {
+            grammar.add_rules(&pack.grammar);
+
+            for (category, entries) in &pack.categories {
+                let pool = categories.entry(category.clone()).or_default();
+                for entry in entries {
+                    match pool.iter_mut().find(|existing| existing.id == entry.id) {
+                        Some(existing) => *existing = entry.clone(),
+                        None => pool.push(entry.clone()),
+                    }
+                }
+            }
+        }
+
+        Self {
+            categories,
+            grammar,
+        }
+    }
+
+    /// Entries available for a category, empty if no enabled pack provides it
+    pub fn entries(&self, category: &str) -> &[PackEntry] {
+        self.categories
+            .get(category)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Grammar rules from all enabled packs
+    pub fn grammar(&self) -> &Grammar {
+        &self.grammar
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_pack() -> GeneratorPack {
+        parse_pack(Path::new(DEFAULT_PACK_PATH), DEFAULT_PACK).unwrap()
+    }
+
+    fn ids(pools: &WordPools, category: &str) -> Vec<String> {
+        pools.entries(category).iter().map(|entry| entry.id.clone()).collect()
+    }
+
+    #[test]
+    fn default_pack_parses() {
+        let pack = default_pack();
+        assert_eq!(pack.path, Path::new(DEFAULT_PACK_PATH));
+        for category in [ATTRIBUTE, SPECIES, CHARACTERISTIC, NAME, PRONOUNS, AGE, BACKSTORY] {
+            assert!(!pack.categories[category].is_empty(), "{category}");
+        }
+    }
+
+    #[test]
+    fn broken_toml_points_at_the_line() {
+        let source = "id = \"broken\"\nname = \"Broken\"\n\n[categories]\nspecies = [\n    { id = \"otter\" text = \"otter\" },\n]\n";
+        let error = parse_pack(Path::new("/packs/broken.toml"), source).unwrap_err();
+        assert_eq!(error.path, Path::new("/packs/broken.toml"));
+        assert_eq!(error.line, Some(6));
+        assert!(error.to_string().starts_with("/packs/broken.toml:6: "));
+    }
+
+    #[test]
+    fn broken_json_points_at_the_line() {
+        let source = "{\n  \"id\": \"broken\",\n  \"name\": \"Broken\",\n  \"categories\": {\n    \"species\": [{ \"id\": \"otter\", }]\n  }\n}\n";
+        let error = parse_pack(Path::new("/packs/broken.json"), source).unwrap_err();
+        assert_eq!(error.path, Path::new("/packs/broken.json"));
+        assert_eq!(error.line, Some(5));
+    }
+
+    #[test]
+    fn bad_entries_point_at_the_entry() {
+        let source = "id = \"bad\"\nname = \"Bad\"\n\n[categories]\nspecies = [\n    { id = \"otter\", text = \"otter\" },\n    { id = \"ghost\" },\n]\n";
+        let error = parse_pack(Path::new("bad.toml"), source).unwrap_err();
+        assert_eq!(error.line, Some(7));
+        assert!(error.message.contains("`ghost`"));
+
+        let source = "id = \"bad\"\nname = \"Bad\"\n\n[categories]\nhybrid = [\n    { id = \"otterfox\", text = \"otterfox\", parents = [\"otter\"] },\n]\n";
+        let error = parse_pack(Path::new("bad.toml"), source).unwrap_err();
+        assert_eq!(error.line, Some(6));
+        assert!(error.message.contains("parents"));
+    }
+
+    #[test]
+    fn user_packs_override_and_extend() {
+        let source = r##"
+            id = "mine"
+            name = "Mine"
+
+            [categories]
+            species = [
+                { id = "fox", text = "space fox" },
+                { id = "otter", text = "otter" },
+            ]
+            mood = [{ id = "grumpy", text = "grumpy" }]
+
+            [grammar]
+            greeting = ["hi"]
+        "##;
+        let user = parse_pack(Path::new("mine.toml"), source).unwrap();
+        let default = default_pack();
+        let default_species = ids(&WordPools::merge(std::slice::from_ref(&default), &[]), SPECIES);
+
+        let pools = WordPools::merge(&[default.clone(), user.clone()], &[]);
+        let species = ids(&pools, SPECIES);
+        // Fox keeps its place but takes the user's text, otter goes at the end
+        assert_eq!(species.len(), default_species.len() + 1);
+        assert_eq!(species[..default_species.len()], default_species[..]);
+        assert_eq!(species.last().map(String::as_str), Some("otter"));
+        let fox = pools.entries(SPECIES).iter().find(|entry| entry.id == "fox").unwrap();
+        assert_eq!(fox.label(), "space fox");
+        assert_eq!(ids(&pools, "mood"), ["grumpy"]);
+        assert_eq!(pools.grammar().expand("#greeting#", &mut fastrand::Rng::with_seed(1)), "hi");
+
+        // Switching the user pack off brings the default back
+        let pools = WordPools::merge(&[default, user], &["mine".to_string()]);
+        assert_eq!(ids(&pools, SPECIES), default_species);
+        assert!(pools.entries("mood").is_empty());
+    }
+}
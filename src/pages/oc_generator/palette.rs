@@ -0,0 +1,250 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Color palettes for OCs
+//!
+//! Every OC gets a main, accent and marking color. The main color is picked inside the range
+//! its species entry declares in the pack (foxes are orange-ish, wolves are grey-ish...), then
+//! the other two follow a color harmony rule so the whole thing looks like it belongs together.
+
+use crate::fl;
+
+/// Mixed into the species seed so the palette doesn't just echo the species roll
+const PALETTE_SALT: u64 = 0x5EED_C010_12ED;
+
+/// Size of one swatch in `swatch_svg`, and the gap between them
+const SWATCH_SIZE: usize = 24;
+const SWATCH_GAP: usize = 4;
+
+/// Where a species' main color can land, in HSL. Hue ranges may wrap around, like `[340, 20]`
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize)]
+#[serde(default)]
+pub struct PaletteRange {
+    pub hue: [f32; 2],
+    pub saturation: [f32; 2],
+    pub lightness: [f32; 2],
+}
+
+impl Default for PaletteRange {
+    fn default() -> Self {
+        Self {
+            hue: [0.0, 360.0],
+            saturation: [0.35, 0.85],
+            lightness: [0.3, 0.7],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Harmony {
+    Complementary,
+    Analogous,
+    Triadic,
+}
+
+impl Harmony {
+    pub const ALL: [Harmony; 3] = [
+        Harmony::Complementary,
+        Harmony::Analogous,
+        Harmony::Triadic,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            Harmony::Complementary => fl!("harmony-complementary"),
+            Harmony::Analogous => fl!("harmony-analogous"),
+            Harmony::Triadic => fl!("harmony-triadic"),
+        }
+    }
+
+    /// Hue offsets for the accent and marking colors
+    fn offsets(self) -> (f32, f32) {
+        match self {
+            Harmony::Complementary => (180.0, 0.0),
+            Harmony::Analogous => (30.0, -30.0),
+            Harmony::Triadic => (120.0, 240.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum SwatchRole {
+    Main,
+    Accent,
+    Marking,
+}
+
+impl SwatchRole {
+    pub fn label(self) -> String {
+        match self {
+            SwatchRole::Main => fl!("swatch-main"),
+            SwatchRole::Accent => fl!("swatch-accent"),
+            SwatchRole::Marking => fl!("swatch-marking"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Swatch {
+    pub role: SwatchRole,
+    /// `#RRGGBB`
+    pub hex: String,
+}
+
+impl Swatch {
+    /// Whether `hex` really is `#RRGGBB`, it ends up in SVG attributes as it is
+    pub fn is_valid(&self) -> bool {
+        self.hex.len() == 7
+            && self.hex.starts_with('#')
+            && self.hex[1..].chars().all(|c| c.is_ascii_hexdigit())
+    }
+
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        let channel = |range| u8::from_str_radix(self.hex.get(range).unwrap_or("00"), 16).unwrap_or(0);
+        (channel(1..3), channel(3..5), channel(5..7))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Palette {
+    pub harmony: Harmony,
+    pub swatches: Vec<Swatch>,
+}
+
+/// Palette file formats we can export
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteFormat {
+    HexList,
+    Gimp,
+}
+
+impl PaletteFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            PaletteFormat::HexList => "txt",
+            PaletteFormat::Gimp => "gpl",
+        }
+    }
+}
+
+impl Palette {
+    /// Roll a palette for a species from a seed
+    pub fn generate(range: &PaletteRange, seed: u64) -> Self {
+        let mut rng = fastrand::Rng::with_seed(seed ^ PALETTE_SALT);
+        let harmony = Harmony::ALL[rng.usize(..Harmony::ALL.len())];
+
+        let [hue_start, mut hue_end] = range.hue;
+        if hue_end < hue_start {
+            hue_end += 360.0;
+        }
+        let main_hue = between(&mut rng, [hue_start, hue_end]);
+        let saturation = between(&mut rng, range.saturation);
+        let lightness = between(&mut rng, range.lightness);
+
+        let (accent_offset, marking_offset) = harmony.offsets();
+        // Markings read best when they contrast with the main color's lightness
+        let marking_lightness = if lightness > 0.5 {
+            lightness - 0.25
+        } else {
+            lightness + 0.25
+        };
+
+        let swatches = vec![
+            (SwatchRole::Main, main_hue, saturation, lightness),
+            (
+                SwatchRole::Accent,
+                main_hue + accent_offset,
+                (saturation + 0.15).min(1.0),
+                between(&mut rng, [0.4, 0.6]),
+            ),
+            (
+                SwatchRole::Marking,
+                main_hue + marking_offset,
+                saturation * 0.8,
+                marking_lightness,
+            ),
+        ]
+        .into_iter()
+        .map(|(role, hue, saturation, lightness)| Swatch {
+            role,
+            hex: hsl_to_hex(hue, saturation, lightness),
+        })
+        .collect();
+
+        Self { harmony, swatches }
+    }
+
+    /// One `#RRGGBB` per line
+    pub fn to_hex_list(&self) -> String {
+        self.swatches
+            .iter()
+            .map(|swatch| format!("{}\n", swatch.hex))
+            .collect()
+    }
+
+    /// A GIMP/Inkscape/Krita compatible `.gpl` palette
+    pub fn to_gpl(&self, name: &str) -> String {
+        let mut gpl = format!("GIMP Palette\nName: {name}\nColumns: {}\n#\n", self.swatches.len());
+        for swatch in &self.swatches {
+            let (r, g, b) = swatch.rgb();
+            gpl.push_str(&format!("{r:3} {g:3} {b:3}\t{}\n", swatch.role.label()));
+        }
+        gpl
+    }
+
+    pub fn export(&self, format: PaletteFormat, name: &str) -> String {
+        match format {
+            PaletteFormat::HexList => self.to_hex_list(),
+            PaletteFormat::Gimp => self.to_gpl(name),
+        }
+    }
+
+    /// Width and height of `swatch_svg`
+    pub fn swatch_size(&self) -> (f32, f32) {
+        let width = (self.swatches.len() * (SWATCH_SIZE + SWATCH_GAP)).saturating_sub(SWATCH_GAP);
+        (width as f32, SWATCH_SIZE as f32)
+    }
+
+    /// A strip of rounded swatches as SVG, for showing with `widget::svg`
+    pub fn swatch_svg(&self) -> Vec<u8> {
+        let (width, height) = self.swatch_size();
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        );
+        for (index, swatch) in self.swatches.iter().enumerate() {
+            svg.push_str(&format!(
+                r##"<rect x="{}.5" y="0.5" width="{size}" height="{size}" rx="6" fill="{}" stroke="#00000033"/>"##,
+                index * (SWATCH_SIZE + SWATCH_GAP),
+                swatch.hex,
+                size = SWATCH_SIZE - 1,
+            ));
+        }
+        svg.push_str("</svg>");
+        svg.into_bytes()
+    }
+}
+
+fn between(rng: &mut fastrand::Rng, [low, high]: [f32; 2]) -> f32 {
+    low + (high - low) * rng.f32()
+}
+
+fn hsl_to_hex(hue: f32, saturation: f32, lightness: f32) -> String {
+    let hue = hue.rem_euclid(360.0);
+    let saturation = saturation.clamp(0.0, 1.0);
+    let lightness = lightness.clamp(0.0, 1.0);
+
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let m = lightness - chroma / 2.0;
+
+    let (r, g, b) = match hue as u32 / 60 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    let to_byte = |channel: f32| ((channel + m) * 255.0).round() as u8;
+    format!("#{:02X}{:02X}{:02X}", to_byte(r), to_byte(g), to_byte(b))
+}
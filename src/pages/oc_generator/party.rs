@@ -0,0 +1,183 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Party generation
+//!
+//! A one-shot needs a whole cast at once. A party is a handful of OCs with different species,
+//! each given a role in the group, plus a few relationships between them so the table has some
+//! drama to start from. Saving a party puts everyone in a new collection of their own.
+
+use super::character::GeneratedOC;
+use super::relationships::RelationshipKind;
+use crate::fl;
+
+/// Party sizes offered in the dropdown
+pub const SIZES: [u32; 6] = [3, 4, 5, 6, 7, 8];
+
+/// Rerolls per member before giving up on a species nobody else in the party has
+///
+/// Small packs (or a locked-down content rating) may not have enough species to go round.
+pub const SPECIES_ATTEMPTS: usize = 20;
+
+/// What a character does for the group
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Role {
+    Leader,
+    ComicRelief,
+    Rival,
+    Mentor,
+    Heart,
+    Brains,
+    Muscle,
+    Wildcard,
+}
+
+impl Role {
+    /// Every party gets these, in this order, when it's big enough
+    const CORE: [Role; 3] = [Role::Leader, Role::ComicRelief, Role::Rival];
+    /// Handed out at random to everyone else
+    const EXTRA: [Role; 5] = [Role::Mentor, Role::Heart, Role::Brains, Role::Muscle, Role::Wildcard];
+
+    pub fn label(self) -> String {
+        match self {
+            Role::Leader => fl!("role-leader"),
+            Role::ComicRelief => fl!("role-comic-relief"),
+            Role::Rival => fl!("role-rival"),
+            Role::Mentor => fl!("role-mentor"),
+            Role::Heart => fl!("role-heart"),
+            Role::Brains => fl!("role-brains"),
+            Role::Muscle => fl!("role-muscle"),
+            Role::Wildcard => fl!("role-wildcard"),
+        }
+    }
+}
+
+/// One character in a generated party
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub oc: GeneratedOC,
+    pub role: Role,
+}
+
+/// A relationship between two party members, before they're saved and have ids
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bond {
+    /// Indices into `Party::members`
+    pub between: [usize; 2],
+    pub kind: RelationshipKind,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Party {
+    pub members: Vec<Member>,
+    pub bonds: Vec<Bond>,
+    /// Already in the favorites, so saving again would only make duplicates
+    pub saved: bool,
+}
+
+/// Roles for a party of `size`, the core roles first and no role twice until they run out
+pub fn roles(rng: &mut fastrand::Rng, size: usize) -> Vec<Role> {
+    let mut extra = Role::EXTRA.to_vec();
+    rng.shuffle(&mut extra);
+
+    Role::CORE
+        .into_iter()
+        .chain(extra.into_iter().cycle())
+        .take(size)
+        .collect()
+}
+
+/// Relationships to go with the roles
+///
+/// The rival is the leader's rival, then everyone without a relationship yet befriends someone
+/// earlier in the party so nobody is left out. On top of that, one pair may turn out to be
+/// siblings or partners.
+pub fn bonds(rng: &mut fastrand::Rng, roles: &[Role]) -> Vec<Bond> {
+    let mut bonds = vec![];
+    let leader = roles.iter().position(|&role| role == Role::Leader);
+    let rival = roles.iter().position(|&role| role == Role::Rival);
+    if let (Some(leader), Some(rival)) = (leader, rival) {
+        bonds.push(Bond {
+            between: [leader, rival],
+            kind: RelationshipKind::Rival,
+        });
+    }
+
+    for member in 1..roles.len() {
+        if !bonds.iter().any(|bond| bond.between.contains(&member)) {
+            bonds.push(Bond {
+                between: [rng.usize(..member), member],
+                kind: RelationshipKind::Friend,
+            });
+        }
+    }
+
+    if roles.len() >= 2 && rng.bool() {
+        let first = rng.usize(..roles.len());
+        let second = (first + rng.usize(1..roles.len())) % roles.len();
+        let kind = if rng.bool() {
+            RelationshipKind::Sibling
+        } else {
+            RelationshipKind::Partner
+        };
+        // Rivals can be siblings, but a pair only gets one bond of each kind
+        if !bonds
+            .iter()
+            .any(|bond| bond.kind == kind && bond.between.contains(&first) && bond.between.contains(&second))
+        {
+            bonds.push(Bond {
+                between: [first, second],
+                kind,
+            });
+        }
+    }
+
+    bonds
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn core_roles_come_first_and_none_repeat_until_they_run_out() {
+        let mut rng = fastrand::Rng::with_seed(3);
+        for size in SIZES {
+            let roles = roles(&mut rng, size as usize);
+            assert_eq!(roles.len(), size as usize);
+            assert_eq!(roles[..3], Role::CORE);
+            for (i, role) in roles.iter().enumerate() {
+                assert!(!roles[..i].contains(role), "{role:?} twice in {roles:?}");
+            }
+        }
+
+        let roles = roles(&mut rng, 10);
+        assert_eq!(roles[8..], roles[3..5]);
+    }
+
+    #[test]
+    fn everyone_has_a_bond() {
+        let mut rng = fastrand::Rng::with_seed(5);
+        for size in SIZES {
+            for _ in 0..50 {
+                let roles = roles(&mut rng, size as usize);
+                let bonds = bonds(&mut rng, &roles);
+
+                assert_eq!(
+                    bonds[0],
+                    Bond {
+                        between: [0, 2],
+                        kind: RelationshipKind::Rival,
+                    }
+                );
+                for member in 0..roles.len() {
+                    assert!(bonds.iter().any(|bond| bond.between.contains(&member)));
+                }
+                for (i, bond) in bonds.iter().enumerate() {
+                    assert_ne!(bond.between[0], bond.between[1]);
+                    assert!(bond.between.iter().all(|&member| member < roles.len()));
+                    assert!(!bonds[..i].contains(bond), "{bond:?} twice in {bonds:?}");
+                }
+            }
+        }
+    }
+}
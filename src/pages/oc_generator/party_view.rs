@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The party panel
+//!
+//! Who plays which role in the last party and how they get along, with the name for the
+//! collection it gets saved in.
+
+use super::character::TraitSlot;
+use super::party::Party;
+use super::{swatches, Message, OcGeneratorPage};
+use crate::fl;
+use cosmic::iced::alignment::Vertical;
+use cosmic::iced::Length;
+use cosmic::widget::{self, column, icon, row, text};
+use cosmic::{cosmic_theme, prelude::*, theme};
+
+impl OcGeneratorPage {
+    /// The last party: who plays which role and how they get along
+    pub(super) fn party_panel<'a>(&'a self, party: &'a Party) -> Element<'a, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_xs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let member_name = |index: usize| {
+            party.members[index]
+                .oc
+                .get(TraitSlot::Name)
+                .map(|t| t.text.clone())
+                .unwrap_or_default()
+        };
+
+        let cards = party
+            .members
+            .iter()
+            .enumerate()
+            .map(|(index, member)| {
+                column::column()
+                    .spacing(space_xxs)
+                    .push(text::heading(member.role.label()))
+                    .push(
+                        widget::button::text(member.oc.text(self.pools.grammar()))
+                            .on_press(Message::ShowPartyMember(index)),
+                    )
+                    .push_maybe(member.oc.palette.as_ref().map(swatches))
+                    .padding(space_xs)
+                    .width(Length::Fixed(280.0))
+                    .apply(widget::container)
+                    .class(theme::Container::Card)
+                    .into()
+            })
+            .collect::<Vec<Element<'_, Message>>>();
+
+        let mut bonds = column::column().spacing(space_xxs);
+        for bond in &party.bonds {
+            let [first, second] = bond.between;
+            bonds = bonds.push(text::body(fl!(
+                "party-bond",
+                first = member_name(first),
+                kind = bond.kind.label(),
+                second = member_name(second)
+            )));
+        }
+
+        let save_button = widget::button::standard(fl!("save-party-button"));
+        let save_button = if party.saved {
+            save_button
+        } else {
+            save_button.on_press(Message::SaveParty)
+        };
+
+        column::column()
+            .spacing(space_s)
+            .push(
+                row()
+                    .spacing(space_s)
+                    .align_y(Vertical::Center)
+                    .push(text::heading(fl!("party", count = party.members.len())))
+                    .push(widget::horizontal_space())
+                    .push_maybe(party.saved.then(|| text::caption(fl!("saved"))))
+                    .push(
+                        widget::text_input(fl!("party-name-placeholder"), &self.party_name_input)
+                            .on_input(Message::PartyNameChanged)
+                            .width(Length::Fixed(220.0)),
+                    )
+                    .push(save_button.class(theme::Button::Suggested))
+                    .push(
+                        widget::button::icon(icon::from_name("window-close-symbolic"))
+                            .on_press(Message::CloseParty),
+                    ),
+            )
+            .push(
+                widget::scrollable(
+                    column::column()
+                        .spacing(space_s)
+                        .push(
+                            widget::flex_row(cards)
+                                .row_spacing(space_xs)
+                                .column_spacing(space_xs),
+                        )
+                        .push(bonds),
+                )
+                .height(Length::Fixed(320.0)),
+            )
+            .into()
+    }
+}
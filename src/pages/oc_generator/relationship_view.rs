@@ -0,0 +1,162 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Relationship views
+//!
+//! The editor on a character's detail view and the graph of everyone with a relationship.
+//! The relationships themselves live in `relationships.rs`, the canvas in `graph.rs`.
+
+use super::relationships::RelationshipKind;
+use super::{graph, CharacterId, Message, OcGeneratorPage};
+use crate::fl;
+use cosmic::iced::alignment::{Horizontal, Vertical};
+use cosmic::iced::Length;
+use cosmic::widget::{self, column, icon, row, text};
+use cosmic::{cosmic_theme, prelude::*, theme};
+
+impl OcGeneratorPage {
+    /// Everyone with a relationship, drawn as a graph
+    pub(super) fn graph_view(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, space_m, ..
+        } = theme::active().cosmic().spacing;
+
+        let mut graph = graph::RelationshipGraph::default();
+        let mut node_ids: Vec<CharacterId> = vec![];
+        for character in &self.saved_characters {
+            if !self.relationships.iter().any(|r| r.involves(character.id)) {
+                continue;
+            }
+
+            let label = if self.is_hidden(character) {
+                fl!("hidden-character")
+            } else {
+                character.sheet.title().to_string()
+            };
+            let color = character
+                .palette
+                .as_ref()
+                .and_then(|palette| palette.swatches.first())
+                .map(|swatch| graph::hex_color(&swatch.hex))
+                .unwrap_or(graph::hex_color("#7A5AF8"));
+            graph.nodes.push(graph::Node { label, color });
+            node_ids.push(character.id);
+        }
+        for relationship in &self.relationships {
+            let [first, second] = relationship
+                .between
+                .map(|id| node_ids.iter().position(|&node| node == id));
+            if let (Some(first), Some(second)) = (first, second) {
+                graph.edges.push(graph::Edge {
+                    between: [first, second],
+                    label: relationship.kind.label(),
+                    color: graph::hex_color(relationship.kind.color()),
+                });
+            }
+        }
+
+        let body: Element<'_, Message> = if graph.nodes.is_empty() {
+            text::body(fl!("no-relationships"))
+                .apply(widget::container)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .align_x(Horizontal::Center)
+                .align_y(Vertical::Center)
+                .into()
+        } else {
+            cosmic::iced::widget::canvas(graph)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        };
+
+        column::column()
+            .spacing(space_s)
+            .push(
+                text::title2(fl!("relationships"))
+                    .apply(widget::container)
+                    .width(Length::Fill)
+                    .align_x(Horizontal::Center),
+            )
+            .push(body)
+            .push(
+                row().spacing(space_m).push(
+                    widget::button::standard(fl!("back-button"))
+                        .on_press(Message::ShowRelationshipGraph(false)),
+                ),
+            )
+            .padding([0, space_xxs])
+            .apply(widget::container)
+            .width(Length::Fill)
+            .height(Length::FillPortion(3))
+            .into()
+    }
+
+    /// A character's relationships, with a row for adding another
+    pub(super) fn relationship_editor(&self, id: CharacterId) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_s, ..
+        } = theme::active().cosmic().spacing;
+
+        let mut list = column::column().spacing(space_xxs);
+        for relationship in self.relationships.iter().filter(|r| r.involves(id)) {
+            let Some(other) = relationship.other(id).and_then(|other| self.character(other)) else {
+                continue;
+            };
+            let title = if self.is_hidden(other) {
+                fl!("hidden-character")
+            } else {
+                other.sheet.title().to_string()
+            };
+
+            list = list.push(
+                row()
+                    .spacing(space_s)
+                    .align_y(Vertical::Center)
+                    .push(text::body(relationship.kind.label()).width(Length::Fixed(140.0)))
+                    .push(widget::button::link(title).on_press(Message::OpenCharacter(other.id)))
+                    .push(widget::horizontal_space())
+                    .push(
+                        widget::button::icon(icon::from_name("list-remove-symbolic"))
+                            .on_press(Message::RemoveRelationship(relationship.clone())),
+                    ),
+            );
+        }
+
+        let draft = &self.relationship_draft;
+        let is_custom = draft.kind >= RelationshipKind::PRESETS.len();
+        let add_button = widget::button::standard(fl!("add-relationship-button"));
+        let add_button = if draft.target.is_some() && draft.kind().is_some() {
+            add_button.on_press(Message::AddRelationship)
+        } else {
+            add_button
+        };
+
+        let add_row = row()
+            .spacing(space_s)
+            .align_y(Vertical::Center)
+            .push(widget::dropdown(
+                &self.relationship_kind_labels,
+                Some(draft.kind),
+                Message::RelationshipKindSelected,
+            ))
+            .push_maybe(is_custom.then(|| {
+                widget::text_input(fl!("relationship-custom-placeholder"), &draft.custom)
+                    .on_input(Message::RelationshipCustomChanged)
+                    .width(Length::Fixed(160.0))
+            }))
+            .push(text::body(fl!("relationship-with")))
+            .push(widget::dropdown(
+                &self.relationship_target_labels,
+                draft.target,
+                Message::RelationshipTargetSelected,
+            ))
+            .push(add_button);
+
+        column::column()
+            .spacing(space_xxs)
+            .push(text::heading(fl!("relationships")))
+            .push(list)
+            .push_maybe((!self.relationship_targets.is_empty()).then_some(add_row))
+            .into()
+    }
+}
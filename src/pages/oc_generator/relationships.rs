@@ -0,0 +1,244 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Relationships between saved OCs
+//!
+//! Every relationship is one undirected edge between two characters, stored once in the
+//! characters config instead of on both characters, so the two sides can never disagree.
+//! Deleting a character takes its edges into the trash with it.
+
+use super::{CharacterId, TrashedOC};
+use crate::fl;
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RelationshipKind {
+    Friend,
+    Rival,
+    Sibling,
+    Partner,
+    /// Anything else, named by the user ("mentor", "roommate"...)
+    Custom(String),
+}
+
+impl RelationshipKind {
+    /// The kinds offered in the editor, `Custom` comes after these
+    pub const PRESETS: [RelationshipKind; 4] = [
+        RelationshipKind::Friend,
+        RelationshipKind::Rival,
+        RelationshipKind::Sibling,
+        RelationshipKind::Partner,
+    ];
+
+    pub fn label(&self) -> String {
+        match self {
+            RelationshipKind::Friend => fl!("relationship-friend"),
+            RelationshipKind::Rival => fl!("relationship-rival"),
+            RelationshipKind::Sibling => fl!("relationship-sibling"),
+            RelationshipKind::Partner => fl!("relationship-partner"),
+            RelationshipKind::Custom(name) => name.clone(),
+        }
+    }
+
+    /// Line color in the graph, as `#RRGGBB`
+    pub fn color(&self) -> &'static str {
+        match self {
+            RelationshipKind::Friend => "#4CAF7A",
+            RelationshipKind::Rival => "#E0555A",
+            RelationshipKind::Sibling => "#5A8FE0",
+            RelationshipKind::Partner => "#E06FB5",
+            RelationshipKind::Custom(_) => "#A8A8B3",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Relationship {
+    pub between: [CharacterId; 2],
+    pub kind: RelationshipKind,
+}
+
+impl Relationship {
+    pub fn new(first: CharacterId, second: CharacterId, kind: RelationshipKind) -> Self {
+        Self {
+            between: [first, second],
+            kind,
+        }
+    }
+
+    pub fn involves(&self, id: CharacterId) -> bool {
+        self.between.contains(&id)
+    }
+
+    /// The character on the other side from `id`, if `id` is part of this relationship
+    pub fn other(&self, id: CharacterId) -> Option<CharacterId> {
+        match self.between {
+            [first, second] if first == id => Some(second),
+            [first, second] if second == id => Some(first),
+            _ => None,
+        }
+    }
+
+    /// Same two characters and kind, whichever way round they were added
+    pub fn same_as(&self, other: &Relationship) -> bool {
+        self.kind == other.kind
+            && (self.between == other.between
+                || self.between == [other.between[1], other.between[0]])
+    }
+}
+
+/// Take a deleted character's relationships off the list, the other side loses them too
+pub fn take_involving(relationships: &mut Vec<Relationship>, id: CharacterId) -> Vec<Relationship> {
+    let (taken, kept) = std::mem::take(relationships)
+        .into_iter()
+        .partition(|r| r.involves(id));
+    *relationships = kept;
+    taken
+}
+
+/// Put a restored character's relationships back
+///
+/// Ones whose other side is saved go back on the list. Ones whose other side is in the trash
+/// too move onto its trash entry, so they come back when it's restored. Ones whose other side
+/// was purged are gone for good.
+pub fn restore(
+    id: CharacterId,
+    taken: Vec<Relationship>,
+    relationships: &mut Vec<Relationship>,
+    trash: &mut [TrashedOC],
+    is_saved: impl Fn(CharacterId) -> bool,
+) {
+    for relationship in taken {
+        let Some(other) = relationship.other(id) else {
+            continue;
+        };
+        if is_saved(other) {
+            relationships.push(relationship);
+        } else if let Some(other) = trash.iter_mut().find(|t| t.character.id == other) {
+            other.relationships.push(relationship);
+        }
+    }
+}
+
+/// What's typed into the "add relationship" row of the detail view
+#[derive(Debug, Clone, Default)]
+pub struct RelationshipDraft {
+    /// Index into the page's relationship targets
+    pub target: Option<usize>,
+    /// Index into `RelationshipKind::PRESETS`, one past the end for a custom kind
+    pub kind: usize,
+    pub custom: String,
+}
+
+impl RelationshipDraft {
+    /// The kind picked in the draft, `None` for a custom kind without a name yet
+    pub fn kind(&self) -> Option<RelationshipKind> {
+        match RelationshipKind::PRESETS.get(self.kind) {
+            Some(kind) => Some(kind.clone()),
+            None => {
+                let name = self.custom.trim();
+                (!name.is_empty()).then(|| RelationshipKind::Custom(name.to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::oc_generator::character::CharacterSheet;
+    use crate::pages::oc_generator::SavedOC;
+
+    /// Saved characters, the relationship list and the trash, deleting the way the page does
+    struct Characters {
+        saved: Vec<CharacterId>,
+        relationships: Vec<Relationship>,
+        trash: Vec<TrashedOC>,
+    }
+
+    impl Characters {
+        fn new(ids: &[CharacterId], relationships: Vec<Relationship>) -> Self {
+            Self {
+                saved: ids.to_vec(),
+                relationships,
+                trash: vec![],
+            }
+        }
+
+        fn delete(&mut self, id: CharacterId) {
+            self.saved.retain(|&saved| saved != id);
+            let relationships = take_involving(&mut self.relationships, id);
+            self.trash.push(TrashedOC {
+                character: SavedOC::new(id, CharacterSheet::default()),
+                deleted_at: 0,
+                relationships,
+            });
+        }
+
+        fn purge(&mut self, id: CharacterId) {
+            self.trash.retain(|t| t.character.id != id);
+        }
+
+        fn restore(&mut self, id: CharacterId) {
+            let position = self.trash.iter().position(|t| t.character.id == id).unwrap();
+            let trashed = self.trash.remove(position);
+            self.saved.push(id);
+            let saved = &self.saved;
+            restore(id, trashed.relationships, &mut self.relationships, &mut self.trash, |other| {
+                saved.contains(&other)
+            });
+        }
+    }
+
+    fn friends(first: CharacterId, second: CharacterId) -> Relationship {
+        Relationship::new(first, second, RelationshipKind::Friend)
+    }
+
+    #[test]
+    fn deleting_either_side_removes_it_for_both() {
+        for deleted in [1, 2] {
+            let mut characters = Characters::new(&[1, 2, 3], vec![friends(1, 2), friends(2, 3)]);
+            characters.delete(deleted);
+            assert!(characters.relationships.iter().all(|r| !r.involves(deleted)));
+            assert!(characters.trash[0].relationships.contains(&friends(1, 2)));
+        }
+    }
+
+    #[test]
+    fn restoring_brings_it_back() {
+        for deleted in [1, 2] {
+            let mut characters = Characters::new(&[1, 2], vec![friends(1, 2)]);
+            characters.delete(deleted);
+            characters.restore(deleted);
+            assert_eq!(characters.relationships, [friends(1, 2)]);
+        }
+    }
+
+    #[test]
+    fn survives_both_sides_in_the_trash() {
+        for (first, second) in [(1, 2), (2, 1)] {
+            for restored_first in [first, second] {
+                let restored_second = if restored_first == first { second } else { first };
+                let mut characters = Characters::new(&[1, 2], vec![friends(1, 2)]);
+                characters.delete(first);
+                characters.delete(second);
+
+                characters.restore(restored_first);
+                assert!(characters.relationships.is_empty());
+                characters.restore(restored_second);
+                assert_eq!(characters.relationships, [friends(1, 2)]);
+            }
+        }
+    }
+
+    #[test]
+    fn purging_either_side_drops_it() {
+        for (purged, restored) in [(1, 2), (2, 1)] {
+            let mut characters = Characters::new(&[1, 2], vec![friends(1, 2)]);
+            characters.delete(1);
+            characters.delete(2);
+            characters.purge(purged);
+            characters.restore(restored);
+            assert!(characters.relationships.is_empty());
+            assert!(characters.trash.is_empty());
+        }
+    }
+}
@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Seed codes
+//!
+//! Every generated OC comes from a seed so it can be shared and rebuilt later. Seeds are kept to
+//! 40 bits so they fit in 8 characters of Crockford base32, which is short enough to paste in
+//! chat and forgiving to type (no I, L, O or U, and case doesn't matter). OCs whose traits came
+//! from different seeds get one code per trait, joined with dashes.
+
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const SEED_BITS: u32 = 40;
+const CODE_LEN: usize = (SEED_BITS / 5) as usize;
+
+/// Draw a new seed from the given generator
+pub fn new_seed(rng: &mut fastrand::Rng) -> u64 {
+    rng.u64(..1 << SEED_BITS)
+}
+
+/// Turn a seed into its shareable code
+pub fn encode(seed: u64) -> String {
+    (0..CODE_LEN)
+        .rev()
+        .map(|i| ALPHABET[((seed >> (i * 5)) & 0x1f) as usize] as char)
+        .collect()
+}
+
+/// Parse a seed code, ignoring case and spaces
+pub fn decode(code: &str) -> Option<u64> {
+    let mut seed: u64 = 0;
+    let mut len = 0;
+
+    for c in code.chars().filter(|c| !c.is_whitespace()) {
+        // Crockford lets people mistype the look-alike letters
+        let c = match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        };
+        let value = ALPHABET.iter().position(|&a| a as char == c)?;
+        seed = (seed << 5) | value as u64;
+        len += 1;
+    }
+
+    (len == CODE_LEN).then_some(seed)
+}
+
+/// Join several seeds into one dash separated code
+pub fn encode_all(seeds: &[u64]) -> String {
+    seeds
+        .iter()
+        .map(|&seed| encode(seed))
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Parse a single or dash separated seed code
+pub fn decode_all(code: &str) -> Option<Vec<u64>> {
+    code.split('-').map(decode).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let mut rng = fastrand::Rng::with_seed(7);
+        for seed in [0, 1, (1 << SEED_BITS) - 1, new_seed(&mut rng)] {
+            let code = encode(seed);
+            assert_eq!(code.len(), CODE_LEN);
+            assert_eq!(decode(&code), Some(seed));
+        }
+    }
+
+    #[test]
+    fn forgives_look_alikes_case_and_spaces() {
+        assert_eq!(decode("0000 0001"), Some(1));
+        assert_eq!(decode("oooooooi"), Some(1));
+        assert_eq!(decode("OOOOOOOL"), Some(1));
+        assert_eq!(decode(&encode(0xABCDE).to_lowercase()), Some(0xABCDE));
+    }
+
+    #[test]
+    fn rejects_bad_codes() {
+        assert_eq!(decode(""), None);
+        assert_eq!(decode("0000000"), None);
+        assert_eq!(decode("000000000"), None);
+        // U isn't in the alphabet
+        assert_eq!(decode("0000000U"), None);
+    }
+
+    #[test]
+    fn dash_codes() {
+        let seeds = [3, 1 << 20, 12345];
+        let code = encode_all(&seeds);
+        assert_eq!(code.matches('-').count(), 2);
+        assert_eq!(decode_all(&code), Some(seeds.to_vec()));
+        assert_eq!(decode_all(&encode(9)), Some(vec![9]));
+        assert_eq!(decode_all(&format!("{}-", encode(9))), None);
+        assert_eq!(decode_all(&format!("{}-0U", encode(9))), None);
+    }
+}
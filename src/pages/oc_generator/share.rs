@@ -0,0 +1,201 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! Copying an OC to the clipboard
+//!
+//! Quicker than exporting a file when an OC just needs to go into a chat or a doc. Markdown and
+//! JSON are the same as the file exports, so a copied JSON OC can be pasted into a file and
+//! imported again.
+
+use super::character::SheetField;
+use super::export::{self, ExportFormat};
+use super::SavedOC;
+use crate::fl;
+
+/// Discord won't send messages longer than this
+const DISCORD_LIMIT: usize = 2000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ShareFormat {
+    #[default]
+    PlainText,
+    Markdown,
+    Discord,
+    Json,
+}
+
+impl ShareFormat {
+    pub const ALL: [ShareFormat; 4] = [
+        ShareFormat::PlainText,
+        ShareFormat::Markdown,
+        ShareFormat::Discord,
+        ShareFormat::Json,
+    ];
+
+    pub fn label(self) -> String {
+        match self {
+            ShareFormat::PlainText => fl!("format-plain-text"),
+            ShareFormat::Markdown => fl!("format-markdown"),
+            ShareFormat::Discord => fl!("format-discord"),
+            ShareFormat::Json => fl!("format-json"),
+        }
+    }
+}
+
+/// An OC as text to paste somewhere
+pub fn share(character: &SavedOC, format: ShareFormat) -> Result<String, String> {
+    match format {
+        ShareFormat::PlainText => Ok(to_plain_text(character)),
+        ShareFormat::Markdown => export::export(&[character], ExportFormat::Markdown),
+        ShareFormat::Discord => Ok(to_discord(character)),
+        ShareFormat::Json => export::export(&[character], ExportFormat::Json),
+    }
+}
+
+/// The short fields as label and value, skipping the name and empty ones
+fn short_fields(character: &SavedOC) -> Vec<(String, String)> {
+    let sheet = &character.sheet;
+    let mut fields: Vec<(String, String)> = SheetField::ALL
+        .into_iter()
+        .filter(|field| {
+            !matches!(field, SheetField::Name | SheetField::Description | SheetField::Notes)
+        })
+        .map(|field| (field.label(), sheet.field(field)))
+        .filter(|(_, value)| !value.is_empty())
+        .collect();
+
+    if !character.tags.is_empty() {
+        fields.push((fl!("tags"), character.tags.join(", ")));
+    }
+    fields
+}
+
+fn to_plain_text(character: &SavedOC) -> String {
+    let sheet = &character.sheet;
+    let mut text = format!("{}\n", sheet.title());
+
+    for (label, value) in short_fields(character) {
+        text.push_str(&format!("{label}: {value}\n"));
+    }
+    if let Some(palette) = &character.palette {
+        let colors: Vec<String> = palette
+            .swatches
+            .iter()
+            .map(|swatch| format!("{} {}", swatch.role.label(), swatch.hex))
+            .collect();
+        text.push_str(&format!("{}: {}\n", fl!("palette"), colors.join(", ")));
+    }
+    for paragraph in [&sheet.description, &sheet.notes] {
+        if !paragraph.is_empty() {
+            text.push_str(&format!("\n{paragraph}\n"));
+        }
+    }
+
+    text
+}
+
+/// A bold title and everything else in a quote block, which reads well in a Discord channel
+fn to_discord(character: &SavedOC) -> String {
+    let sheet = &character.sheet;
+    let mut lines = vec![format!("**{}**", sheet.title())];
+
+    for (label, value) in short_fields(character) {
+        lines.push(format!("> **{label}:** {value}"));
+    }
+    if let Some(palette) = &character.palette {
+        let colors: Vec<String> = palette
+            .swatches
+            .iter()
+            .map(|swatch| format!("`{}`", swatch.hex))
+            .collect();
+        lines.push(format!("> **{}:** {}", fl!("palette"), colors.join(" ")));
+    }
+    for paragraph in [&sheet.description, &sheet.notes] {
+        if !paragraph.is_empty() {
+            lines.push(">".to_string());
+            lines.extend(paragraph.lines().map(|line| format!("> {line}")));
+        }
+    }
+
+    let text = lines.join("\n");
+    if text.chars().count() <= DISCORD_LIMIT {
+        return text;
+    }
+    // Leave room for the ellipsis so the whole thing still sends in one message
+    let mut truncated: String = text.chars().take(DISCORD_LIMIT - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::oc_generator::character::CharacterSheet;
+    use crate::pages::oc_generator::palette::{Harmony, Palette, Swatch, SwatchRole};
+
+    fn character() -> SavedOC {
+        let sheet = CharacterSheet {
+            name: "Ash".to_string(),
+            species: "fox".to_string(),
+            description: "A tall, shy fox.".to_string(),
+            notes: "Likes rain.\nHates mornings.".to_string(),
+            ..Default::default()
+        };
+        let mut character = SavedOC::new(7, sheet);
+        character.tags = vec!["fursona".to_string(), "main".to_string()];
+        character.palette = Some(Palette {
+            harmony: Harmony::Complementary,
+            swatches: vec![Swatch {
+                role: SwatchRole::Main,
+                hex: "#FF8800".to_string(),
+            }],
+        });
+        character
+    }
+
+    #[test]
+    fn plain_text() {
+        let text = share(&character(), ShareFormat::PlainText).unwrap();
+        let expected = format!(
+            "Ash\n{}: fox\n{}: fursona, main\n{}: {} #FF8800\n\n\
+             A tall, shy fox.\n\nLikes rain.\nHates mornings.\n",
+            SheetField::Species.label(),
+            fl!("tags"),
+            fl!("palette"),
+            SwatchRole::Main.label(),
+        );
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn discord_quotes_everything_but_the_title() {
+        let text = share(&character(), ShareFormat::Discord).unwrap();
+        let expected = format!(
+            "**Ash**\n> **{}:** fox\n> **{}:** fursona, main\n> **{}:** `#FF8800`\n\
+             >\n> A tall, shy fox.\n>\n> Likes rain.\n> Hates mornings.",
+            SheetField::Species.label(),
+            fl!("tags"),
+            fl!("palette"),
+        );
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn discord_fits_in_one_message() {
+        let mut character = character();
+        character.sheet.backstory = "ö".repeat(DISCORD_LIMIT);
+        let text = share(&character, ShareFormat::Discord).unwrap();
+        assert_eq!(text.chars().count(), DISCORD_LIMIT);
+        assert!(text.ends_with('…'));
+    }
+
+    #[test]
+    fn markdown_and_json_match_the_exports() {
+        let character = character();
+        assert_eq!(
+            share(&character, ShareFormat::Markdown),
+            export::export(&[&character], ExportFormat::Markdown)
+        );
+        let json = share(&character, ShareFormat::Json).unwrap();
+        assert_eq!(export::import_json(&json).unwrap()[0].sheet, character.sheet);
+    }
+}
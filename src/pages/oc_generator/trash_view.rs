@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! The trash bin
+//!
+//! Deleted characters wait here until they are restored, purged by hand or too old for the
+//! retention period.
+
+use super::{favorites, Message, OcGeneratorPage, TRASH_RETENTION_DAYS};
+use crate::fl;
+use cosmic::iced::alignment::{Horizontal, Vertical};
+use cosmic::iced::Length;
+use cosmic::widget::{self, column, icon, row, text};
+use cosmic::{cosmic_theme, prelude::*, theme};
+
+impl OcGeneratorPage {
+    /// Deleted characters that can still be restored
+    pub(super) fn trash_view(&self) -> Element<'_, Message> {
+        let cosmic_theme::Spacing {
+            space_xxs, space_xs, space_s, space_m, ..
+        } = theme::active().cosmic().spacing;
+
+        let selected_retention = TRASH_RETENTION_DAYS
+            .iter()
+            .position(|&days| days == self.config.trash_retention_days);
+
+        let mut list = widget::list::list_column();
+        for trashed in self.trash.iter().rev() {
+            let id = trashed.character.id;
+            let title = if self.is_hidden(&trashed.character) {
+                fl!("hidden-character")
+            } else {
+                trashed.character.sheet.title().to_string()
+            };
+            list = list
+                .add(
+                    row()
+                        .spacing(space_xs)
+                        .align_y(Vertical::Center)
+                        .padding(space_xs)
+                        .push(
+                            column::column()
+                                .push(text::heading(title))
+                                .push(text::caption(fl!(
+                                    "deleted-on",
+                                    date = favorites::format_date(trashed.deleted_at)
+                                )))
+                                .width(Length::Fill),
+                        )
+                        .push(
+                            widget::button::standard(fl!("restore-button"))
+                                .on_press(Message::RestoreCharacter(id)),
+                        )
+                        .push(
+                            widget::button::icon(icon::from_name("edit-delete-symbolic"))
+                                .on_press(Message::PurgeCharacter(id))
+                                .class(theme::Button::Destructive),
+                        ),
+                )
+                .spacing(space_xs);
+        }
+
+        let empty_button = if self.trash.is_empty() {
+            widget::button::destructive(fl!("empty-trash-button"))
+        } else {
+            widget::button::destructive(fl!("empty-trash-button")).on_press(Message::EmptyTrash)
+        };
+
+        column::column()
+            .spacing(space_s)
+            .push(
+                text::title2(fl!("trash"))
+                    .apply(widget::container)
+                    .width(Length::Fill)
+                    .align_x(Horizontal::Center),
+            )
+            .push(
+                row()
+                    .spacing(space_s)
+                    .align_y(Vertical::Center)
+                    .push(text::body(fl!("trash-retention-label")))
+                    .push(widget::dropdown(
+                        &self.retention_labels,
+                        selected_retention,
+                        Message::TrashRetentionChanged,
+                    )),
+            )
+            .push(widget::scrollable(list).height(Length::Fill))
+            .push(
+                row()
+                    .spacing(space_m)
+                    .push(
+                        widget::button::standard(fl!("back-button"))
+                            .on_press(Message::ShowTrash(false)),
+                    )
+                    .push(widget::horizontal_space())
+                    .push(empty_button),
+            )
+            .padding([0, space_xxs])
+            .apply(widget::container)
+            .width(Length::Fill)
+            .height(Length::FillPortion(3))
+            .into()
+    }
+}
@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: MPL-2.0
+
+//! "More like this"
+//!
+//! Liking or disliking an OC nudges a weight for every pack entry it was rolled from. The
+//! weights don't change what a seed rolls, that would break seed codes and rebuilding saved
+//! characters. Instead a fresh roll tries a few seeds and picks one of them with a chance that
+//! grows with how well liked its entries are, so every OC is still exactly what its seed gives.
+//!
+//! Weights live in the app config keyed like `species/fox`, in percent of a neutral pick.
+
+use super::character::OcTrait;
+use std::collections::BTreeMap;
+
+/// Weight of an entry nobody rated yet
+pub const NEUTRAL: u32 = 100;
+/// Weights stay in this range so one entry can't crowd out or vanish from a pool for good
+const MIN_WEIGHT: u32 = 25;
+const MAX_WEIGHT: u32 = 800;
+/// How much one like raises a weight, in percent, and one dislike lowers it
+const LIKE_STEP: u32 = 125;
+const DISLIKE_STEP: u32 = 80;
+
+/// Seeds tried per roll when the bias is above zero
+pub const CANDIDATES: usize = 16;
+
+/// The config keys for a trait's entries, both parents for a hybrid species
+pub fn keys(oc_trait: &OcTrait) -> Vec<String> {
+    let category = oc_trait.slot.category();
+    if oc_trait.parents.is_empty() {
+        vec![format!("{category}/{}", oc_trait.entry_id)]
+    } else {
+        oc_trait
+            .parents
+            .iter()
+            .map(|parent| format!("{category}/{}", parent.entry_id))
+            .collect()
+    }
+}
+
+/// Nudge the weights of every entry in an OC up for a like or down for a dislike
+pub fn rate(weights: &mut BTreeMap<String, u32>, traits: &[OcTrait], liked: bool) {
+    for key in traits.iter().filter(|t| !t.entry_id.is_empty()).flat_map(keys) {
+        let weight = weights.entry(key).or_insert(NEUTRAL);
+        let step = if liked { LIKE_STEP } else { DISLIKE_STEP };
+        *weight = (*weight * step / 100).clamp(MIN_WEIGHT, MAX_WEIGHT);
+    }
+    // Back at neutral is the same as never rated, keep the config tidy
+    weights.retain(|_, weight| *weight != NEUTRAL);
+}
+
+/// How much an OC should be favored, `bias` going from 0 (all the same) to 100 (full weights)
+pub fn score(weights: &BTreeMap<String, u32>, traits: &[OcTrait], bias: u32) -> f64 {
+    let exponent = f64::from(bias.min(100)) / 100.0;
+    traits
+        .iter()
+        .flat_map(keys)
+        .map(|key| {
+            let weight = weights.get(&key).copied().unwrap_or(NEUTRAL);
+            (f64::from(weight) / f64::from(NEUTRAL)).powf(exponent)
+        })
+        .product()
+}
+
+/// Index of a score, picked at random in proportion to the scores
+pub fn pick(rng: &mut fastrand::Rng, scores: &[f64]) -> usize {
+    let total: f64 = scores.iter().sum();
+    let mut roll = rng.f64() * total;
+    for (index, score) in scores.iter().enumerate() {
+        if roll < *score {
+            return index;
+        }
+        roll -= score;
+    }
+    scores.len().saturating_sub(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pages::oc_generator::character::{ParentSpecies, TraitSlot};
+
+    fn oc_trait(slot: TraitSlot, entry_id: &str) -> OcTrait {
+        OcTrait {
+            slot,
+            entry_id: entry_id.to_string(),
+            text: entry_id.to_string(),
+            seed: 0,
+            parents: vec![],
+            features: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn rating_nudges_weights() {
+        let traits = [
+            oc_trait(TraitSlot::Attribute, "tall"),
+            oc_trait(TraitSlot::Species, "fox"),
+            oc_trait(TraitSlot::Name, ""),
+        ];
+        let mut weights = BTreeMap::new();
+
+        rate(&mut weights, &traits, true);
+        assert_eq!(
+            weights,
+            BTreeMap::from([("attribute/tall".to_string(), 125), ("species/fox".to_string(), 125)])
+        );
+
+        // A dislike undoes a like, and neutral weights aren't kept
+        rate(&mut weights, &traits, false);
+        assert!(weights.is_empty());
+    }
+
+    #[test]
+    fn weights_stay_in_range() {
+        let traits = [oc_trait(TraitSlot::Species, "fox")];
+        let mut liked = BTreeMap::new();
+        let mut disliked = BTreeMap::new();
+        for _ in 0..50 {
+            rate(&mut liked, &traits, true);
+            rate(&mut disliked, &traits, false);
+        }
+        assert_eq!(liked["species/fox"], MAX_WEIGHT);
+        assert_eq!(disliked["species/fox"], MIN_WEIGHT);
+    }
+
+    #[test]
+    fn hybrids_rate_both_parents() {
+        let mut hybrid = oc_trait(TraitSlot::Species, "fox+wolf");
+        hybrid.parents = ["fox", "wolf"]
+            .map(|id| ParentSpecies {
+                entry_id: id.to_string(),
+                text: id.to_string(),
+            })
+            .to_vec();
+        assert_eq!(keys(&hybrid), ["species/fox", "species/wolf"]);
+    }
+
+    #[test]
+    fn scores_follow_the_bias() {
+        let traits = [oc_trait(TraitSlot::Species, "fox")];
+        let weights = BTreeMap::from([("species/fox".to_string(), 400)]);
+        assert_eq!(score(&weights, &traits, 0), 1.0);
+        assert_eq!(score(&weights, &traits, 50), 2.0);
+        assert_eq!(score(&weights, &traits, 100), 4.0);
+        assert_eq!(score(&BTreeMap::new(), &traits, 100), 1.0);
+    }
+
+    #[test]
+    fn picks_in_proportion() {
+        let mut rng = fastrand::Rng::with_seed(3);
+        assert_eq!(pick(&mut rng, &[0.0, 1.0, 0.0]), 1);
+        assert_eq!(pick(&mut rng, &[]), 0);
+
+        let picks = (0..10_000).filter(|_| pick(&mut rng, &[1.0, 3.0]) == 1).count();
+        assert!((7_000..8_000).contains(&picks), "{picks}");
+    }
+}